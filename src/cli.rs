@@ -82,6 +82,15 @@ impl Arg {
         self.requires.push(requires);
         self
     }
+
+    /**
+     * Checks if a command line argument is the short or long flag of this argument
+     * Either flag may be left unset, in which case it never matches
+     */
+    fn is_flag(&self, env_arg: &str) -> bool {
+        self.short.is_some_and(|short| env_arg == format!("-{short}"))
+            || self.long.is_some_and(|long| env_arg == format!("--{long}"))
+    }
 }
 
 impl<'a> Command<'a> {
@@ -121,13 +130,7 @@ impl<'a> Command<'a> {
     fn check_if_required_args_are_present(&self, env_args: &[String], arg: &Arg) {
         for required in &arg.requires {
             let required_arg = self.find_arg(required).unwrap();
-            if !env_args
-                .iter()
-                .any(|s| *s == format!("-{}", required_arg.short.unwrap()))
-                && !env_args
-                    .iter()
-                    .any(|s| *s == format!("--{}", required_arg.long.unwrap()))
-            {
+            if !env_args.iter().any(|s| required_arg.is_flag(s)) {
                 println!(
                     "The argument \"{}\" requires the argument \"{}\"",
                     arg.name, required
@@ -171,10 +174,7 @@ impl<'a> Command<'a> {
             .and_then(|args| args.iter().find(|&arg| arg.name == arg_name))
             .map(|arg| {
                 let args: Vec<String> = env::args().collect();
-                let found = args.iter().any(|s| {
-                    *s == format!("-{}", arg.short.unwrap())
-                        || *s == format!("--{}", arg.long.unwrap())
-                });
+                let found = args.iter().any(|s| arg.is_flag(s));
 
                 if found {
                     self.check_if_required_args_are_present(&args, arg);
@@ -194,10 +194,7 @@ impl<'a> Command<'a> {
             .and_then(|args| args.iter().find(|&arg| arg.name == arg_name))
             .and_then(|arg| {
                 let args: Vec<String> = env::args().collect();
-                let arg_index = args.iter().position(|s| {
-                    *s == format!("-{}", arg.short.unwrap())
-                        || *s == format!("--{}", arg.long.unwrap())
-                });
+                let arg_index = args.iter().position(|s| arg.is_flag(s));
 
                 let value = arg_index.and_then(|index| args.get(index + 1));
                 value.or(arg.default.as_ref()).map(|s| s.to_string())
@@ -1,4 +1,6 @@
+use crate::Error;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Comparison {
@@ -514,6 +516,35 @@ impl Age {
     }
 }
 
+/**
+ * Parses an age given by the user, either as its dictionary key or by name (ex: C or classical)
+ */
+impl FromStr for Age {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = s.trim().to_lowercase();
+        let age = match key.as_str() {
+            "archaic" => Age::Archaic,
+            "early" => Age::Early,
+            "classical" => Age::Classical,
+            "late" => Age::Late,
+            "later" => Age::Later,
+            "medieval" => Age::Medieval,
+            "scholar" => Age::Scholar,
+            "modern" => Age::Modern,
+            key if key.len() == 1 => Age::dict_key_to_age(&key.to_uppercase()),
+            _ => Age::Unknown,
+        };
+
+        if age == Age::Unknown {
+            Err(Error::InvalidAge(s.to_string()))
+        } else {
+            Ok(age)
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Area {
     Agriculture,
@@ -569,6 +600,38 @@ impl Area {
     }
 }
 
+/**
+ * Parses an area given by the user, either as its dictionary key or by name (ex: L or legal)
+ */
+impl FromStr for Area {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = s.trim().to_lowercase();
+        let area = match key.as_str() {
+            "agriculture" => Area::Agriculture,
+            "biological" => Area::Biological,
+            "art" => Area::Art,
+            "religious" | "ecclesiastical" => Area::Religious,
+            "grammar" => Area::Grammar,
+            "legal" => Area::Legal,
+            "poetic" => Area::Poetic,
+            "scientific" => Area::Scientific,
+            "technical" => Area::Technical,
+            "warfare" => Area::Warfare,
+            "mythological" => Area::Mythological,
+            key if key.len() == 1 => Area::dict_key_to_area(&key.to_uppercase()),
+            _ => Area::Unknown,
+        };
+
+        if area == Area::Unknown {
+            Err(Error::InvalidArea(s.to_string()))
+        } else {
+            Ok(area)
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Geography {
     Africa,
@@ -642,6 +705,44 @@ impl Geography {
     }
 }
 
+/**
+ * Parses a geography given by the user, either as its dictionary key or by name (ex: I or italy)
+ */
+impl FromStr for Geography {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = s.trim().to_lowercase();
+        let geography = match key.as_str() {
+            "africa" => Geography::Africa,
+            "britain" => Geography::Britain,
+            "china" => Geography::China,
+            "scandinavia" => Geography::Scandinavia,
+            "egypt" => Geography::Egypt,
+            "france" | "gaul" => Geography::FranceGaul,
+            "germany" => Geography::Germany,
+            "greece" => Geography::Greece,
+            "italy" | "rome" => Geography::ItalyRome,
+            "india" => Geography::India,
+            "balkans" => Geography::Balkans,
+            "netherlands" => Geography::Netherlands,
+            "persia" => Geography::Persia,
+            "near_east" => Geography::NearEast,
+            "russia" => Geography::Russia,
+            "spain" | "iberia" => Geography::SpainIberia,
+            "eastern_europe" => Geography::EasternEurope,
+            key if key.len() == 1 => Geography::dict_key_to_geography(&key.to_uppercase()),
+            _ => Geography::Unknown,
+        };
+
+        if geography == Geography::Unknown {
+            Err(Error::InvalidGeography(s.to_string()))
+        } else {
+            Ok(geography)
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Frequency {
     VeryFrequent,
@@ -797,3 +898,48 @@ impl Source {
         }
     }
 }
+
+/**
+ * Parses a source given by the user, either as its dictionary key or by name (ex: S or lewis_short)
+ */
+impl FromStr for Source {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let key = s.trim().to_lowercase();
+        let source = match key.as_str() {
+            "beeson" => Source::Beeson,
+            "beard" => Source::Beard,
+            "adams" => Source::Adams,
+            "stelten" => Source::Stelten,
+            "deferrari" => Source::Deferrari,
+            "gildersleeve" => Source::Gildersleeve,
+            "collatinus" => Source::Collatinus,
+            "leverett" => Source::Leverett,
+            "novus" => Source::Novus,
+            "lewis" => Source::Lewis,
+            "latham" => Source::Latham,
+            "nelson" => Source::Nelson,
+            "oxford" => Source::Oxford,
+            "souter" => Source::Souter,
+            "other" => Source::Other,
+            "plater_white" => Source::PlaterWhite,
+            "lewis_short" => Source::LewisShort,
+            "translation" => Source::Translation,
+            "du_cange" => Source::DuCange,
+            "vademecum" => Source::Vademecum,
+            "personal_guess" => Source::PersonalGuess,
+            "temp_special_code" => Source::TempSpecialCode,
+            "sent_by_user" => Source::SentByUser,
+            "general" => Source::General,
+            key if key.len() == 1 => Source::dict_key_to_source(&key.to_uppercase()),
+            _ => Source::Unknown,
+        };
+
+        if source == Source::Unknown {
+            Err(Error::InvalidSource(s.to_string()))
+        } else {
+            Ok(source)
+        }
+    }
+}
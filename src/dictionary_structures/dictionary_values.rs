@@ -1028,6 +1028,7 @@ pub enum ModifierType {
     Tackon,
     Packon,
     NotPackon,
+    Tickon,
     Unspecified,
 }
 
@@ -1039,6 +1040,7 @@ impl ModifierType {
             ModifierType::Tackon => "enclitic tackon",
             ModifierType::Packon => "enclitic packon",
             ModifierType::NotPackon => "enclitic not packon",
+            ModifierType::Tickon => "tickon",
             ModifierType::Unspecified => "unspecified",
         }
     }
@@ -1671,10 +1673,88 @@ impl<'de> Deserialize<'de> for Attachment {
         let orth = serde_json::from_value(map.remove("orth").unwrap())
             .expect("Failed to deserialize orth");
 
+        // the pos is followed by the form of the words the attachment is used with (ex: "PRON 5 0 PERS")
+        let pos = pos.split_whitespace().next().unwrap_or("");
+
         Ok(Attachment {
-            pos: PartOfSpeech::dict_key_to_part_of_speech(&pos),
+            pos: PartOfSpeech::dict_key_to_part_of_speech(pos),
             senses,
             orth,
         })
     }
 }
+
+/**
+ * A prefix written differently before some letters (ad + fero -> affero).
+ */
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Assimilation {
+    pub prefix: String,
+    pub form: String,
+    pub before: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NameKind {
+    Praenomen,
+    Gentilicium,
+    Cognomen,
+    Place,
+    #[default]
+    #[serde(other)]
+    Other,
+}
+
+impl NameKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NameKind::Praenomen => "praenomen",
+            NameKind::Gentilicium => "gentilicium",
+            NameKind::Cognomen => "cognomen",
+            NameKind::Place => "place name",
+            NameKind::Other => "proper name",
+        }
+    }
+}
+
+/**
+ * A name from a user supplied names list, declined like a noun.
+ * ex: { "orth": "Marcus", "stem": "Marc", "n": [2, 1], "gender": "M", "kind": "praenomen" }
+ */
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LatinName {
+    pub orth: String,
+    pub stem: String,
+    pub n: Vec<i8>,
+    #[serde(default)]
+    pub gender: Option<String>,
+    #[serde(default)]
+    pub kind: NameKind,
+    #[serde(default)]
+    pub senses: Vec<String>,
+}
+
+/**
+ * A word of a phrase, matched either by how it is written or by the dictionary entry it is a form of.
+ */
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PhraseWord {
+    #[serde(default)]
+    pub form: Option<String>,
+    #[serde(default)]
+    pub lemma: Option<String>,
+}
+
+/**
+ * A phrase whose meaning is not the one of its words, `max_gap` is how many other words can be between two of its words.
+ * ex: ne ... quidem, gratias (tibi) agere
+ */
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LatinPhrase {
+    pub phrase: String,
+    pub words: Vec<PhraseWord>,
+    #[serde(default)]
+    pub max_gap: usize,
+    pub senses: Vec<String>,
+}
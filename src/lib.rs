@@ -1,8 +1,10 @@
 #![feature(stmt_expr_attributes)]
 
+use std::collections::HashSet;
 use std::fmt::Display;
+use std::mem::take;
 
-use crate::{translators::{english_to_latin::translate_english_to_latin, latin_to_english::translate_latin_to_english, Language, Translation, TranslationType}, utils::{data::{get_english_dictionary, get_latin_dictionary}, sanitize_word}};
+use crate::{translators::{english_to_latin::translate_english_to_latin, filter::WordFilter, latin_to_english::{checker::{check_sentence, Issue}, difficulty::DifficultyReport, disambiguation::{rank_readings, SentenceAnalysis, WordAnalysis}, frequency::{FrequencyList, MergeBy}, glossary::Glossary, lemmatizer::LemmatizedToken, names::{find_names, guess_proper_name}, phrases::{find_phrases, PhraseCandidate}, suggestions::suggest, find_latin_words, translate_latin_to_english, LatinTranslationInfo}, process_latin_definition, Language, Translation, TranslationType}, utils::{data::{get_english_dictionary, get_latin_dictionary}, ocr::clean_ocr_text, sanitize_word, tokenizer::{sentence_word_tokens, english_word_tokens, SourceSpan, Token}}};
 
 pub mod dictionary_structures;
 pub mod translators;
@@ -13,45 +15,428 @@ pub fn latin_to_english(
     latin_text: &str,
     max: usize,
     tricks: bool,
+    medieval: bool,
     sort: bool,
+    filter: &WordFilter,
+    suggestions: usize,
 ) -> Vec<Translation> {
-    let latin_words: Vec<&str> = latin_text.split(" ").collect();
     let mut translations: Vec<Translation> = Vec::new();
+    let mut phrase_candidates: Vec<PhraseCandidate> = Vec::new();
 
-    for word in latin_words {
-        let mut definitions = translate_latin_to_english(&sanitize_word(word), tricks);
+    // punctuation, numbers and whitespace are not looked up
+    for (sentence, token) in sentence_word_tokens(latin_text) {
+        let word = token.text.as_str();
+        let sanitized_word = sanitize_word(word);
+        let mut definitions = look_up_latin_word(word, &sanitized_word, tricks, medieval, true);
+
+        // words that are not in the dictionary, or were only guessed from their ending, may be misspelled
+        let not_found = definitions
+            .iter()
+            .all(|definition| definition.guessed_from.is_some());
+        let word_suggestions = if not_found && suggestions > 0 {
+            Some(suggest(&sanitized_word, suggestions))
+        } else {
+            None
+        };
+
+        // phrases are matched against every reading of the word, not only the ones that are shown
+        phrase_candidates.push(PhraseCandidate::new(
+            &sanitized_word,
+            &definitions,
+            sentence,
+        ));
+
+        definitions.retain(|definition| filter.fits(&definition.word.info));
         definitions.truncate(max);
         let mut translation =
             Translation::new(word.to_string(), TranslationType::Latin(definitions));
+        translation.suggestions = word_suggestions;
+        translation.normalized = Some(sanitized_word);
+        translation.span = Some(token.span);
 
         translation.post_process(Language::Latin, sort);
         translations.push(translation);
     }
 
+    // each word of a phrase gets the phrase, next to what the word means on its own
+    for phrase in find_phrases(&phrase_candidates) {
+        for &index in &phrase.words {
+            translations[index]
+                .phrases
+                .get_or_insert_with(Vec::new)
+                .push(phrase.clone());
+        }
+    }
+
+    translations
+}
+
+/**
+ * Every definition of a word of a text, the names lists being checked before the dictionary.
+ * Without `guess`, a word missing from the dictionary is not guessed from its ending (see `find_latin_words`).
+ */
+fn look_up_latin_word(
+    word: &str,
+    sanitized_word: &str,
+    tricks: bool,
+    medieval: bool,
+    guess: bool,
+) -> Vec<LatinTranslationInfo> {
+    // the names lists are checked first, so that names that are also Latin words come before them
+    let mut definitions = find_names(sanitized_word).unwrap_or_default();
+    let found_name = !definitions.is_empty();
+    let found_words = if guess {
+        translate_latin_to_english(sanitized_word, tricks, medieval)
+    } else {
+        find_latin_words(sanitized_word, tricks, medieval)
+    };
+    definitions.extend(
+        found_words
+            .into_iter()
+            .filter(|definition| !found_name || definition.guessed_from.is_none()),
+    );
+
+    // a capitalized word that is not found, or was only guessed from its ending, is most likely a name
+    let not_found = definitions
+        .iter()
+        .all(|definition| definition.guessed_from.is_some());
+    if not_found && word.starts_with(char::is_uppercase) {
+        if let Some(names) = guess_proper_name(sanitized_word) {
+            definitions = names;
+        }
+    }
+
+    definitions
+}
+
+/**
+ * The words of each sentence of a text, with every definition of each word (see `look_up_latin_word`).
+ */
+fn look_up_latin_sentences(
+    latin_text: &str,
+    tricks: bool,
+    medieval: bool,
+    guess: bool,
+) -> Vec<(Vec<Token>, Vec<Vec<LatinTranslationInfo>>)> {
+    let tokens = sentence_word_tokens(latin_text);
+
+    tokens
+        .chunk_by(|(first, _), (second, _)| first == second)
+        .map(|sentence_tokens| {
+            sentence_tokens
+                .iter()
+                .map(|(_, token)| {
+                    let sanitized_word = sanitize_word(&token.text);
+                    let definitions =
+                        look_up_latin_word(&token.text, &sanitized_word, tricks, medieval, guess);
+                    (token.clone(), definitions)
+                })
+                .unzip()
+        })
+        .collect()
+}
+
+/**
+ * Splits text into sentences and ranks the readings of each word by how well they fit the words around them:
+ * prepositions and the case they take, adjectives and the nouns they agree with, verbs and their subjects.
+ * Each reading is one definition with one of its inflections, the best first, with the reasons it was moved up.
+ */
+pub fn analyze_latin_sentences(
+    latin_text: &str,
+    max: usize,
+    tricks: bool,
+    medieval: bool,
+    filter: &WordFilter,
+) -> Vec<SentenceAnalysis> {
+    let mut sentences: Vec<SentenceAnalysis> = Vec::new();
+
+    for (tokens, mut definitions) in look_up_latin_sentences(latin_text, tricks, medieval, true) {
+        for word_definitions in definitions.iter_mut() {
+            word_definitions.retain(|definition| filter.fits(&definition.word.info));
+        }
+        let words: Vec<String> = tokens.iter().map(|token| token.text.clone()).collect();
+
+        let analyzed_words: Vec<WordAnalysis> = tokens
+            .iter()
+            .zip(rank_readings(&words, &definitions))
+            .map(|(token, mut readings)| {
+                readings.truncate(max);
+                for reading in readings.iter_mut() {
+                    reading.definition = process_latin_definition(take(&mut reading.definition));
+                }
+
+                WordAnalysis {
+                    word: token.text.clone(),
+                    normalized: sanitize_word(&token.text),
+                    span: token.span,
+                    readings,
+                    corrections: None,
+                }
+            })
+            .collect();
+
+        // the sentence runs from its first word to its last, the punctuation around it is left out
+        let (first, last) = match (analyzed_words.first(), analyzed_words.last()) {
+            (Some(first), Some(last)) => (first.span, last.span),
+            _ => continue,
+        };
+        let span = first.to(&last);
+
+        sentences.push(SentenceAnalysis {
+            text: latin_text[span.start..span.end].to_string(),
+            span,
+            words: analyzed_words,
+        });
+    }
+
+    sentences
+}
+
+/**
+ * Looks for likely mistakes in Latin written by a learner, sentence by sentence (see `check_sentence`).
+ * The issues are in the order of the text, with where they are in it.
+ */
+pub fn check_latin_text(latin_text: &str, tricks: bool, medieval: bool) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = Vec::new();
+
+    for (tokens, definitions) in look_up_latin_sentences(latin_text, tricks, medieval, true) {
+        issues.extend(check_sentence(&tokens, &definitions));
+    }
+
+    issues
+}
+
+/**
+ * Gives the lemma of each word of a text, the best one by the words around it (see `rank_readings`),
+ * and the other lemmas it can have.
+ * Words are looked up as by `latin_to_english`, with the tricks and medieval spellings asked for,
+ * but words missing from the dictionary are not guessed from their ending, as the guesses are not lemmas.
+ */
+pub fn lemmatize(latin_text: &str, tricks: bool, medieval: bool) -> Vec<LemmatizedToken> {
+    let mut lemmatized: Vec<LemmatizedToken> = Vec::new();
+
+    for (tokens, definitions) in look_up_latin_sentences(latin_text, tricks, medieval, false) {
+        let words: Vec<String> = tokens.iter().map(|token| token.text.clone()).collect();
+
+        for (token, readings) in tokens.iter().zip(rank_readings(&words, &definitions)) {
+            lemmatized.push(LemmatizedToken::new(token, &readings));
+        }
+    }
+
+    lemmatized
+}
+
+/**
+ * Lemmatizes each file of a corpus and counts its lemmas, or its forms, the most frequent first (see `FrequencyList`).
+ */
+pub fn latin_frequency_list(
+    files: &[String],
+    merge_by: MergeBy,
+    tricks: bool,
+    medieval: bool,
+) -> anyhow::Result<FrequencyList> {
+    let mut lemmatized_files: Vec<(String, Vec<LemmatizedToken>)> = Vec::new();
+
+    for file in files {
+        let text = std::fs::read_to_string(file)
+            .map_err(|err| Error::InvalidCorpusFile(format!("{file}: {err}")))?;
+        lemmatized_files.push((file.clone(), lemmatize(&text, tricks, medieval)));
+    }
+
+    Ok(FrequencyList::new(lemmatized_files, merge_by))
+}
+
+/**
+ * Reports how hard a text is to read (see `DifficultyReport`), and how much of it `known_words` (headwords) covers if it is given.
+ */
+pub fn latin_text_difficulty(
+    latin_text: &str,
+    known_words: Option<&[String]>,
+    tricks: bool,
+    medieval: bool,
+) -> DifficultyReport {
+    let known_words: Option<HashSet<String>> = known_words.map(|known_words| {
+        known_words
+            .iter()
+            .map(|word| word.trim().to_lowercase())
+            .collect()
+    });
+
+    DifficultyReport::new(
+        &lemmatize(latin_text, tricks, medieval),
+        known_words.as_ref(),
+    )
+}
+
+/**
+ * Makes a running glossary of a passage: every lemma that is not in `known_words` (headwords),
+ * in the order it first appears in, with the lines it is on.
+ * The best lemma of each word is used (see `rank_readings`), words that are not found are left out.
+ */
+pub fn reading_glossary(
+    latin_text: &str,
+    known_words: &[String],
+    tricks: bool,
+    medieval: bool,
+) -> Glossary {
+    let known_words: HashSet<String> = known_words
+        .iter()
+        .map(|word| word.trim().to_lowercase())
+        .collect();
+    let mut glossary = Glossary::new();
+
+    for (tokens, definitions) in look_up_latin_sentences(latin_text, tricks, medieval, false) {
+        let words: Vec<String> = tokens.iter().map(|token| token.text.clone()).collect();
+
+        for (token, readings) in tokens.iter().zip(rank_readings(&words, &definitions)) {
+            let best = readings
+                .into_iter()
+                .find(|reading| reading.definition.guessed_from.is_none());
+
+            if let Some(reading) = best {
+                let definition = process_latin_definition(reading.definition);
+                glossary.add(&definition, token.span.line, &known_words);
+            }
+        }
+    }
+
+    glossary
+}
+
+/**
+ * Translates text from a scanned edition, cleaning it first (see `utils::ocr`).
+ * Each translation has the corrections made to its word, and to the marks removed just before it.
+ */
+pub fn ocr_latin_to_english(
+    latin_text: &str,
+    max: usize,
+    tricks: bool,
+    medieval: bool,
+    sort: bool,
+    filter: &WordFilter,
+    suggestions: usize,
+) -> Vec<Translation> {
+    let cleaned = clean_ocr_text(latin_text);
+    let mut translations = latin_to_english(
+        &cleaned.text,
+        max,
+        tricks,
+        medieval,
+        sort,
+        filter,
+        suggestions,
+    );
+
+    // the words were found in the cleaned text, but their spans have to be in the given text
+    let source_ranges: Vec<(usize, usize)> = translations
+        .iter()
+        .map(|translation| {
+            translation
+                .span
+                .map_or((0, 0), |span| cleaned.source_range(span.start, span.end))
+        })
+        .collect();
+    let spans = SourceSpan::from_byte_ranges(latin_text, &source_ranges);
+    for (translation, span) in translations.iter_mut().zip(spans) {
+        translation.span = Some(span);
+    }
+
+    for correction in cleaned.corrections {
+        let word_index = correction_word_index(&source_ranges, correction.position);
+        if let Some(translation) = translations.get_mut(word_index) {
+            translation
+                .corrections
+                .get_or_insert_with(Vec::new)
+                .push(correction);
+        }
+    }
+
     translations
 }
 
+/**
+ * Analyzes text from a scanned edition (see `analyze_latin_sentences`), after cleaning it up like `ocr_latin_to_english` does.
+ * The spans are in the given text, and each word has the changes made to it.
+ */
+pub fn ocr_analyze_latin_sentences(
+    latin_text: &str,
+    max: usize,
+    tricks: bool,
+    medieval: bool,
+    filter: &WordFilter,
+) -> Vec<SentenceAnalysis> {
+    let cleaned = clean_ocr_text(latin_text);
+    let mut sentences = analyze_latin_sentences(&cleaned.text, max, tricks, medieval, filter);
+
+    // the words were found in the cleaned text, but their spans have to be in the given text
+    let source_ranges: Vec<(usize, usize)> = sentences
+        .iter()
+        .flat_map(|sentence| &sentence.words)
+        .map(|word| cleaned.source_range(word.span.start, word.span.end))
+        .collect();
+    let mut spans = SourceSpan::from_byte_ranges(latin_text, &source_ranges).into_iter();
+
+    let mut words: Vec<&mut WordAnalysis> = Vec::new();
+    for sentence in sentences.iter_mut() {
+        for word in sentence.words.iter_mut() {
+            if let Some(span) = spans.next() {
+                word.span = span;
+            }
+        }
+
+        if let (Some(first), Some(last)) = (sentence.words.first(), sentence.words.last()) {
+            sentence.span = first.span.to(&last.span);
+            sentence.text = latin_text[sentence.span.start..sentence.span.end].to_string();
+        }
+        words.extend(sentence.words.iter_mut());
+    }
+
+    for correction in cleaned.corrections {
+        let word_index = correction_word_index(&source_ranges, correction.position);
+        if let Some(word) = words.get_mut(word_index) {
+            word.corrections
+                .get_or_insert_with(Vec::new)
+                .push(correction);
+        }
+    }
+
+    sentences
+}
+
+// the word a correction at this byte of the given text belongs to, the last word for a correction after every word
+fn correction_word_index(source_ranges: &[(usize, usize)], position: usize) -> usize {
+    source_ranges
+        .iter()
+        .position(|&(_, end)| position < end)
+        .unwrap_or(source_ranges.len().saturating_sub(1))
+}
+
 pub fn english_to_latin(
     english_text: &str,
     max: usize,
     sort: bool,
+    filter: &WordFilter,
 ) -> Vec<Translation> {
-    let english_words: Vec<&str> = english_text.split(" ").collect();
     let mut translations: Vec<Translation> = Vec::new();
 
     let latin_dictionary = get_latin_dictionary();
     let english_dictionary = get_english_dictionary();
 
-    for word in english_words {
+    for token in english_word_tokens(english_text) {
+        let word = token.text.as_str();
+        let sanitized_word = sanitize_word(word);
         let definitions = translate_english_to_latin(
             english_dictionary,
             latin_dictionary,
-            &sanitize_word(word),
+            &sanitized_word,
             max,
             sort,
+            filter,
         );
         let mut translation =
             Translation::new(word.to_string(), TranslationType::English(definitions));
+        translation.normalized = Some(sanitized_word);
+        translation.span = Some(token.span);
         translation.post_process(Language::English, sort);
         translations.push(translation);
     }
@@ -65,6 +450,17 @@ pub enum Error {
     InvalidWordType(String),
     InvalidRomanNumeral(String),
     InvalidNumber(String),
+    InvalidTrickFile(String),
+    TricksAlreadyLoaded,
+    InvalidNamesFile(String),
+    NamesAlreadyLoaded,
+    InvalidCorpusFile(String),
+    InvalidMergeBy(String),
+    InvalidGlossaryFormat(String),
+    InvalidAge(String),
+    InvalidArea(String),
+    InvalidGeography(String),
+    InvalidSource(String),
 }
 
 impl Display for Error {
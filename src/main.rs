@@ -4,9 +4,18 @@ use std::str::FromStr;
 
 use cli::{Arg, Cli, Command, ArgValue};
 use vocab_vault::dictionary_structures::dictionary_keys::PartOfSpeech;
+use vocab_vault::translators::filter::WordFilter;
+use vocab_vault::translators::latin_to_english::frequency::MergeBy;
+use vocab_vault::translators::latin_to_english::glossary::GlossaryFormat;
+use vocab_vault::translators::latin_to_english::lemmatizer::TSV_HEADER;
 use vocab_vault::translators::Language;
 use vocab_vault::use_data::{get_list, WordType};
-use vocab_vault::{english_to_latin, latin_to_english};
+use vocab_vault::utils::data::{load_user_names, load_user_tricks};
+use vocab_vault::{
+    analyze_latin_sentences, check_latin_text, english_to_latin, latin_frequency_list,
+    latin_text_difficulty, latin_to_english, lemmatize, ocr_analyze_latin_sentences,
+    ocr_latin_to_english, reading_glossary, Error,
+};
 
 use vocab_vault::{translators::DisplayType};
 //TODO: add a command for searching a word by id in english or latin dictionary
@@ -40,6 +49,72 @@ fn main() {
             .with_long("detailed")
             .with_help("Adds more information to the pretty output")
             .requires("pretty"),
+        Arg::new()
+            .with_name("age")
+            .with_long("age")
+            .with_value_name("AGES")
+            .with_help("Only include words from these ages, separated by commas"),
+        Arg::new()
+            .with_name("exclude_age")
+            .with_long("exclude-age")
+            .with_value_name("AGES")
+            .with_help("Exclude words from these ages, separated by commas"),
+        Arg::new()
+            .with_name("area")
+            .with_long("area")
+            .with_value_name("AREAS")
+            .with_help("Only include words from these areas, separated by commas"),
+        Arg::new()
+            .with_name("exclude_area")
+            .with_long("exclude-area")
+            .with_value_name("AREAS")
+            .with_help("Exclude words from these areas, separated by commas"),
+        Arg::new()
+            .with_name("geo")
+            .with_long("geo")
+            .with_value_name("GEOS")
+            .with_help("Only include words from these regions, separated by commas"),
+        Arg::new()
+            .with_name("exclude_geo")
+            .with_long("exclude-geo")
+            .with_value_name("GEOS")
+            .with_help("Exclude words from these regions, separated by commas"),
+        Arg::new()
+            .with_name("source")
+            .with_long("source")
+            .with_value_name("SOURCES")
+            .with_help("Only include words from these sources, separated by commas"),
+        Arg::new()
+            .with_name("exclude_source")
+            .with_long("exclude-source")
+            .with_value_name("SOURCES")
+            .with_help("Exclude words from these sources, separated by commas"),
+        Arg::new()
+            .with_name("classical")
+            .with_short('c')
+            .with_long("classical")
+            .with_help("Only include words from the early and classical periods"),
+    ];
+
+    let global_args_for_text = vec![
+        Arg::new()
+            .with_name("tricks")
+            .with_short('t')
+            .with_long("tricks")
+            .with_help("Will attempt to use various tricks to find the words"),
+        Arg::new()
+            .with_name("medieval")
+            .with_short('e')
+            .with_long("medieval")
+            .with_help(
+                "Will also try medieval spellings (e for ae, ci for ti, h added or lost, ...)",
+            ),
+        Arg::new()
+            .with_name("names")
+            .with_short('n')
+            .with_long("names")
+            .with_value_name("FILES")
+            .with_help("Json files with names to check before the dictionary, separated by commas"),
     ];
 
     let cli = Cli::new().with_default_command("tui").with_commands(vec![
@@ -47,13 +122,144 @@ fn main() {
             .with_args(&global_args_for_translation),
         Command::new("transLat", "Translate latin to english")
             .with_args(&global_args_for_translation)
+            .with_args(&global_args_for_text)
+            .with_arg(
+                Arg::new()
+                    .with_name("trick-file")
+                    .with_short('f')
+                    .with_long("trick-file")
+                    .with_value_name("FILES")
+                    .with_help("Json files with extra tricks to try, separated by commas"),
+            )
             .with_arg(
                 Arg::new()
-                    .with_name("tricks")
-                    .with_short('t')
-                    .with_long("tricks")
-                    .with_help("Will attempt to use various tricks to find the translation"),
+                    .with_name("suggestions")
+                    .with_short('g')
+                    .with_long("suggestions")
+                    .with_value_name("COUNT")
+                    .default("0")
+                    .with_help("The maximum number of close words to suggest when a word is not found"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("ocr")
+                    .with_short('o')
+                    .with_long("ocr")
+                    .with_help("Cleans up text from scanned editions (broken words, ligatures, brackets, OCR mistakes) before translating it"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("analyze")
+                    .with_short('a')
+                    .with_long("analyze")
+                    .with_help("Ranks the readings of each word by the words around it in its sentence, with the reasons"),
             ),
+        Command::new("check", "Checks Latin written by learners for likely mistakes")
+            .with_arg(
+                Arg::new()
+                    .with_name("words")
+                    .with_value_name("TEXT")
+                    .with_help("The Latin text to check"),
+            )
+            .with_args(&global_args_for_text)
+            .with_arg(
+                Arg::new()
+                    .with_name("pretty")
+                    .with_short('p')
+                    .with_long("pretty")
+                    .with_help("Prints the output in a pretty format"),
+            ),
+        Command::new("lemmatize", "Gives the lemma of each word of a latin text")
+            .with_arg(
+                Arg::new()
+                    .with_name("words")
+                    .with_value_name("TEXT")
+                    .with_help("The Latin text to lemmatize"),
+            )
+            .with_args(&global_args_for_text)
+            .with_arg(
+                Arg::new()
+                    .with_name("format")
+                    .with_short('f')
+                    .with_long("format")
+                    .with_value_name("FORMAT")
+                    .default("tsv")
+                    .with_help("The output format: tsv (with a header line) or json (one json object per line)"),
+            ),
+        Command::new("freq", "Counts the lemmas of a corpus of latin text files")
+            .with_arg(
+                Arg::new()
+                    .with_name("files")
+                    .with_value_name("FILES")
+                    .with_help("The text files of the corpus, separated by spaces"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("merge")
+                    .with_short('m')
+                    .with_long("merge")
+                    .with_value_name("BY")
+                    .default("lemma")
+                    .with_help("What the words are counted by: lemma or surface (how they are written)"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("format")
+                    .with_short('f')
+                    .with_long("format")
+                    .with_value_name("FORMAT")
+                    .default("csv")
+                    .with_help("The output format: csv or json"),
+            )
+            .with_args(&global_args_for_text),
+        Command::new("glossary", "Makes a running glossary of a latin passage")
+            .with_arg(
+                Arg::new()
+                    .with_name("file")
+                    .with_value_name("FILE")
+                    .with_help("The text file with the passage"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("known")
+                    .with_short('k')
+                    .with_long("known")
+                    .with_value_name("FILE")
+                    .with_help("A text file with the words the readers know, one headword per line, to leave out"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("format")
+                    .with_short('f')
+                    .with_long("format")
+                    .with_value_name("FORMAT")
+                    .default("markdown")
+                    .with_help("The output format: markdown, latex or html"),
+            )
+            .with_args(&global_args_for_text),
+        Command::new("difficulty", "Reports how hard a latin text is to read")
+            .with_arg(
+                Arg::new()
+                    .with_name("file")
+                    .with_value_name("FILE")
+                    .with_help("The text file to report on"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("known")
+                    .with_short('k')
+                    .with_long("known")
+                    .with_value_name("FILE")
+                    .with_help("A text file with the words the readers know, one headword per line, to report how much of the text they cover"),
+            )
+            .with_arg(
+                Arg::new()
+                    .with_name("pretty")
+                    .with_short('p')
+                    .with_long("pretty")
+                    .with_help("Prints the output in a pretty format"),
+            )
+            .with_args(&global_args_for_text),
         Command::new("getList", "Gets a list of words based on the options provided")
             .with_arg(
                 Arg::new()
@@ -147,8 +353,9 @@ fn main() {
             let sort = command.has("sort");
             let pretty = command.has("pretty");
             let detailed = command.has("detailed");
+            let filter = get_word_filter(command);
 
-            let translations = english_to_latin(&words, max, sort);
+            let translations = english_to_latin(&words, max, sort, &filter);
             if pretty {
                 for translation in translations {
                     translation.display(DisplayType::Pretty(detailed));
@@ -167,9 +374,46 @@ fn main() {
             let sort = command.has("sort");
             let pretty = command.has("pretty");
             let detailed = command.has("detailed");
-            let tricks = command.has("tricks");
+            let (tricks, medieval) = load_translation_options(command);
+            let filter = get_word_filter(command);
+
+            if let ArgValue::Present(trick_files) = command.get_value_of("trick-file") {
+                let trick_files: Vec<String> = trick_files
+                    .split(',')
+                    .map(|file| file.trim().to_string())
+                    .collect();
+                load_user_tricks(&trick_files).unwrap_or_else(|e| {
+                    println!("{e}");
+                    std::process::exit(0);
+                });
+            }
 
-            let translations = latin_to_english(&words, max, tricks, sort);
+            let suggestions = command
+                .get_value_of("suggestions")
+                .throw_if_none()
+                .parse::<usize>()
+                .unwrap();
+            if command.has("analyze") {
+                let sentences = if command.has("ocr") {
+                    ocr_analyze_latin_sentences(&words, max, tricks, medieval, &filter)
+                } else {
+                    analyze_latin_sentences(&words, max, tricks, medieval, &filter)
+                };
+                if pretty {
+                    for sentence in sentences {
+                        sentence.display(DisplayType::Pretty(detailed));
+                    }
+                } else {
+                    println!("{}", serde_json::to_string_pretty(&sentences).unwrap());
+                }
+                return;
+            }
+
+            let translations = if command.has("ocr") {
+                ocr_latin_to_english(&words, max, tricks, medieval, sort, &filter, suggestions)
+            } else {
+                latin_to_english(&words, max, tricks, medieval, sort, &filter, suggestions)
+            };
             if pretty {
                 for translation in translations {
                     translation.display(DisplayType::Pretty(detailed));
@@ -178,6 +422,109 @@ fn main() {
                 println!("{}", serde_json::to_string_pretty(&translations).unwrap());
             }
         }
+        "check" => {
+            let words = command.get_value().throw_if_none();
+            let (tricks, medieval) = load_translation_options(command);
+
+            let issues = check_latin_text(&words, tricks, medieval);
+            if command.has("pretty") {
+                if issues.is_empty() {
+                    println!("No mistakes found");
+                }
+                for issue in issues {
+                    println!("{issue}");
+                }
+            } else {
+                println!("{}", serde_json::to_string_pretty(&issues).unwrap());
+            }
+        }
+        "lemmatize" => {
+            let words = command.get_value().throw_if_none();
+            let (tricks, medieval) = load_translation_options(command);
+            let format = command.get_value_of("format").throw_if_none();
+
+            if format != "tsv" && format != "json" {
+                println!("Invalid format. Please use tsv or json.");
+                std::process::exit(0);
+            }
+
+            let tokens = lemmatize(&words, tricks, medieval);
+            if format == "tsv" {
+                println!("{TSV_HEADER}");
+            }
+            for token in tokens {
+                match format.as_str() {
+                    "tsv" => println!("{}", token.as_tsv()),
+                    _ => println!("{}", token.as_json_line()),
+                }
+            }
+        }
+        "freq" => {
+            let files: Vec<String> = command
+                .get_value()
+                .throw_if_none()
+                .split_whitespace()
+                .map(|file| file.to_string())
+                .collect();
+            let (tricks, medieval) = load_translation_options(command);
+            let format = command.get_value_of("format").throw_if_none();
+            let merge_by = MergeBy::from_str(&command.get_value_of("merge").throw_if_none())
+                .unwrap_or_else(|e| {
+                    println!("{e}");
+                    std::process::exit(0);
+                });
+
+            if format != "csv" && format != "json" {
+                println!("Invalid format. Please use csv or json.");
+                std::process::exit(0);
+            }
+
+            let list =
+                latin_frequency_list(&files, merge_by, tricks, medieval).unwrap_or_else(|e| {
+                    println!("{e}");
+                    std::process::exit(0);
+                });
+            if format == "csv" {
+                println!("{}", list.as_csv());
+            } else {
+                println!("{}", serde_json::to_string_pretty(&list).unwrap());
+            }
+        }
+        "glossary" => {
+            let file = command.get_value().throw_if_none();
+            let (tricks, medieval) = load_translation_options(command);
+            let format = GlossaryFormat::from_str(&command.get_value_of("format").throw_if_none())
+                .unwrap_or_else(|e| {
+                    println!("{e}");
+                    std::process::exit(0);
+                });
+
+            let passage = std::fs::read_to_string(&file).unwrap_or_else(|e| {
+                println!("{file}: {e}");
+                std::process::exit(0);
+            });
+            let known_words = read_known_words(command).unwrap_or_default();
+
+            let glossary = reading_glossary(&passage, &known_words, tricks, medieval);
+            println!("{}", glossary.render(format));
+        }
+        "difficulty" => {
+            let file = command.get_value().throw_if_none();
+            let (tricks, medieval) = load_translation_options(command);
+
+            let text = std::fs::read_to_string(&file).unwrap_or_else(|e| {
+                println!("{file}: {e}");
+                std::process::exit(0);
+            });
+            let known_words = read_known_words(command);
+
+            let report = latin_text_difficulty(&text, known_words.as_deref(), tricks, medieval);
+            if command.has("pretty") {
+                println!("{report}");
+            } else {
+                println!("{}", serde_json::to_string_pretty(&report).unwrap());
+            }
+        }
         "getList" => {
             let type_of_words = command.get_value().throw_if_none();
             let pos = command.get_value_of("pos");
@@ -308,7 +655,15 @@ fn main() {
                     }
                     _ => match language {
                         Language::Latin => {
-                            let translations = latin_to_english(input, 6, true, true);
+                            let translations = latin_to_english(
+                                input,
+                                6,
+                                true,
+                                false,
+                                true,
+                                &WordFilter::new(),
+                                0,
+                            );
                             if false {
                                 for translation in translations {
                                     translation.display(DisplayType::Pretty(false));
@@ -318,7 +673,7 @@ fn main() {
                             }
                         }
                         Language::English => {
-                            let translations = english_to_latin(input, 6, true);
+                            let translations = english_to_latin(input, 6, true, &WordFilter::new());
                             if false {
                                 for translation in translations {
                                     translation.display(DisplayType::Pretty(false));
@@ -337,4 +692,105 @@ fn main() {
     }
 }
 
+/**
+ * Gets the options of the commands that look up latin words, and loads the names files they were given
+ */
+fn load_translation_options(command: &Command) -> (bool, bool) {
+    if let ArgValue::Present(name_files) = command.get_value_of("names") {
+        let name_files: Vec<String> = name_files
+            .split(',')
+            .map(|file| file.trim().to_string())
+            .collect();
+        load_user_names(&name_files).unwrap_or_else(|e| {
+            println!("{e}");
+            std::process::exit(0);
+        });
+    }
+
+    (command.has("tricks"), command.has("medieval"))
+}
+
+/**
+ * Reads the known words file of the command, one word per line, if one was given
+ */
+fn read_known_words(command: &Command) -> Option<Vec<String>> {
+    let known_file = match command.get_value_of("known") {
+        ArgValue::Present(known_file) => known_file,
+        ArgValue::Missing(_) => return None,
+    };
+
+    let known_words = std::fs::read_to_string(&known_file).unwrap_or_else(|e| {
+        println!("{known_file}: {e}");
+        std::process::exit(0);
+    });
+
+    Some(
+        known_words
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.to_string())
+            .collect(),
+    )
+}
+
+fn get_word_filter(command: &Command) -> WordFilter {
+    let mut filter = if command.has("classical") {
+        if let ArgValue::Present(_) = command.get_value_of("age") {
+            println!("The argument \"classical\" can not be used with the argument \"age\"");
+            std::process::exit(0);
+        }
+        WordFilter::classical_only()
+    } else {
+        WordFilter::new()
+    };
+
+    if let Some(ages) = parse_key_list(command.get_value_of("age")) {
+        filter = filter.with_ages(ages);
+    }
+    if let Some(ages) = parse_key_list(command.get_value_of("exclude_age")) {
+        filter = filter.with_exclude_ages(ages);
+    }
+    if let Some(areas) = parse_key_list(command.get_value_of("area")) {
+        filter = filter.with_areas(areas);
+    }
+    if let Some(areas) = parse_key_list(command.get_value_of("exclude_area")) {
+        filter = filter.with_exclude_areas(areas);
+    }
+    if let Some(geos) = parse_key_list(command.get_value_of("geo")) {
+        filter = filter.with_geos(geos);
+    }
+    if let Some(geos) = parse_key_list(command.get_value_of("exclude_geo")) {
+        filter = filter.with_exclude_geos(geos);
+    }
+    if let Some(sources) = parse_key_list(command.get_value_of("source")) {
+        filter = filter.with_sources(sources);
+    }
+    if let Some(sources) = parse_key_list(command.get_value_of("exclude_source")) {
+        filter = filter.with_exclude_sources(sources);
+    }
+
+    filter
+}
+
+/**
+ * Parses a comma separated list of dictionary keys (ex: "C,D") or their names (ex: "classical,late")
+ */
+fn parse_key_list<T: FromStr<Err = Error>>(value: ArgValue) -> Option<Vec<T>> {
+    let value = match value {
+        ArgValue::Present(value) => value,
+        ArgValue::Missing(_) => return None,
+    };
+
+    let list = value
+        .split(',')
+        .map(T::from_str)
+        .collect::<Result<Vec<T>, Error>>()
+        .unwrap_or_else(|e| {
+            println!("{e}");
+            std::process::exit(0);
+        });
+
+    Some(list)
+}
+
 //TODO: get dictionaries here, to not repeat getting them for each word
@@ -7,6 +7,7 @@ use crate::dictionary_structures::dictionary_keys::{
 use crate::dictionary_structures::dictionary_values::{
     EnglishWordInfo, Form, LatinWordInfo, LongForm,
 };
+use crate::translators::filter::WordFilter;
 use crate::utils::{convert_number_to_roman_numeral, is_all_numbers};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -29,6 +30,7 @@ pub fn translate_english_to_latin(
     english_word: &str,
     max: usize,
     sort: bool,
+    filter: &WordFilter,
 ) -> Vec<EnglishTranslationInfo> {
     let mut output: Vec<EnglishTranslationInfo> = Vec::new();
 
@@ -81,6 +83,9 @@ pub fn translate_english_to_latin(
                 EnglishTranslationInfo::new(EnglishWordInfo::new(), LatinWordInfo::new());
 
             if let Some(latin_word) = latin_words_map.get(&word.wid) {
+                if !filter.fits(&latin_word.info) {
+                    continue;
+                }
                 translation.translation.set_word(latin_word);
             }
 
@@ -0,0 +1,117 @@
+use crate::dictionary_structures::dictionary_keys::{Age, Area, Geography, Source};
+use crate::dictionary_structures::dictionary_values::WordInfo;
+
+/**
+ * Restricts translation results by the age, area, geography and source of each entry.
+ * Entries marked as used in all ages / areas / places, and entries with unknown info,
+ * are never removed by an include list, only by an exclude list.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct WordFilter {
+    pub ages: Option<Vec<Age>>,
+    pub exclude_ages: Option<Vec<Age>>,
+    pub areas: Option<Vec<Area>>,
+    pub exclude_areas: Option<Vec<Area>>,
+    pub geos: Option<Vec<Geography>>,
+    pub exclude_geos: Option<Vec<Geography>>,
+    pub sources: Option<Vec<Source>>,
+    pub exclude_sources: Option<Vec<Source>>,
+}
+
+impl WordFilter {
+    pub fn new() -> WordFilter {
+        Self::default()
+    }
+
+    /**
+     * Only keeps words from the early and classical periods
+     */
+    pub fn classical_only() -> WordFilter {
+        WordFilter::new().with_ages(vec![Age::Early, Age::Classical])
+    }
+
+    pub fn with_ages(mut self, ages: Vec<Age>) -> WordFilter {
+        self.ages = Some(ages);
+        self
+    }
+
+    pub fn with_exclude_ages(mut self, ages: Vec<Age>) -> WordFilter {
+        self.exclude_ages = Some(ages);
+        self
+    }
+
+    pub fn with_areas(mut self, areas: Vec<Area>) -> WordFilter {
+        self.areas = Some(areas);
+        self
+    }
+
+    pub fn with_exclude_areas(mut self, areas: Vec<Area>) -> WordFilter {
+        self.exclude_areas = Some(areas);
+        self
+    }
+
+    pub fn with_geos(mut self, geos: Vec<Geography>) -> WordFilter {
+        self.geos = Some(geos);
+        self
+    }
+
+    pub fn with_exclude_geos(mut self, geos: Vec<Geography>) -> WordFilter {
+        self.exclude_geos = Some(geos);
+        self
+    }
+
+    pub fn with_sources(mut self, sources: Vec<Source>) -> WordFilter {
+        self.sources = Some(sources);
+        self
+    }
+
+    pub fn with_exclude_sources(mut self, sources: Vec<Source>) -> WordFilter {
+        self.exclude_sources = Some(sources);
+        self
+    }
+
+    pub fn fits(&self, info: &WordInfo) -> bool {
+        value_fits(
+            &info.age,
+            &self.ages,
+            &self.exclude_ages,
+            &[Age::UsedThroughoutAges, Age::Unknown],
+        ) && value_fits(
+            &info.area,
+            &self.areas,
+            &self.exclude_areas,
+            &[Area::AllOrNone, Area::Unknown],
+        ) && value_fits(
+            &info.geo,
+            &self.geos,
+            &self.exclude_geos,
+            &[Geography::AllOrNone, Geography::Unknown],
+        ) && value_fits(
+            &info.source,
+            &self.sources,
+            &self.exclude_sources,
+            &[Source::General, Source::Unknown],
+        )
+    }
+}
+
+fn value_fits<T: PartialEq>(
+    value: &T,
+    include: &Option<Vec<T>>,
+    exclude: &Option<Vec<T>>,
+    always_included: &[T],
+) -> bool {
+    if let Some(exclude) = exclude {
+        if exclude.contains(value) {
+            return false;
+        }
+    }
+
+    if let Some(include) = include {
+        if !include.contains(value) && !always_included.contains(value) {
+            return false;
+        }
+    }
+
+    true
+}
@@ -0,0 +1,362 @@
+use crate::dictionary_structures::dictionary_keys::{Gender, PartOfSpeech};
+use crate::translators::latin_to_english::disambiguation::{
+    agrees, case_name, genders_agree, number_name, word_features, Features, PREPOSITION_REACH,
+};
+use crate::translators::latin_to_english::suggestions::suggest;
+use crate::translators::latin_to_english::LatinTranslationInfo;
+use crate::utils::sanitize_word;
+use crate::utils::tokenizer::{SourceSpan, Token};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+// words joining two subjects into one plural subject (Marcus et Iulia ambulant), -que is checked on the second word
+const CONJUNCTIONS: [&str; 3] = ["et", "atque", "ac"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueKind {
+    UnknownWord,
+    Agreement,
+    PrepositionCase,
+    SubjectVerb,
+}
+
+impl IssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssueKind::UnknownWord => "unknown word",
+            IssueKind::Agreement => "agreement",
+            IssueKind::PrepositionCase => "preposition case",
+            IssueKind::SubjectVerb => "subject and verb",
+        }
+    }
+}
+
+/**
+ * A likely mistake in a Latin text, `words` are the words it is about and `span` runs from the first to the last of them.
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Issue {
+    pub kind: IssueKind,
+    pub words: Vec<String>,
+    pub span: SourceSpan,
+    pub message: String,
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{} {}: {}",
+            self.span.line,
+            self.span.column,
+            self.kind.as_str(),
+            self.message
+        )
+    }
+}
+
+/**
+ * Looks for likely mistakes in a sentence written by a learner: words that are not found,
+ * a preposition followed by a word in a case it does not take, an adjective and a noun next to each other that almost agree,
+ * and a verb that does not agree in number with any nominative of the sentence (nominatives joined by et, atque, ac or -que
+ * can be the subject of a plural verb).
+ * Only words that can not be read any other way are flagged, so that a correct sentence is not.
+ */
+pub fn check_sentence(tokens: &[Token], definitions: &[Vec<LatinTranslationInfo>]) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = Vec::new();
+
+    // words that were only guessed would give made up features, they are left out of the other checks
+    let features: Vec<Vec<Features>> = tokens
+        .iter()
+        .zip(definitions)
+        .map(|(token, word_definitions)| {
+            if is_unknown(word_definitions) {
+                if let Some(issue) = check_unknown_word(token) {
+                    issues.push(issue);
+                }
+                Vec::new()
+            } else {
+                word_features(word_definitions)
+            }
+        })
+        .collect();
+
+    issues.extend(check_prepositions(tokens, &features));
+    issues.extend(check_agreement(tokens, &features));
+    issues.extend(check_subjects(tokens, &features));
+    issues.sort_by_key(|issue| issue.span.start);
+
+    issues
+}
+
+fn is_unknown(definitions: &[LatinTranslationInfo]) -> bool {
+    definitions
+        .iter()
+        .all(|definition| definition.guessed_from.is_some())
+}
+
+// capitalized words are most likely names, which are not expected to be in the dictionary
+fn check_unknown_word(token: &Token) -> Option<Issue> {
+    if token.text.starts_with(char::is_uppercase) {
+        return None;
+    }
+
+    let mut message = format!("'{}' is not a Latin word", token.text);
+    if let Some(suggestion) = suggest(&sanitize_word(&token.text), 1).first() {
+        message.push_str(&format!(", did you mean '{}'?", suggestion.word));
+    }
+
+    Some(Issue {
+        kind: IssueKind::UnknownWord,
+        words: vec![token.text.clone()],
+        span: token.span,
+        message,
+    })
+}
+
+fn check_prepositions(tokens: &[Token], features: &[Vec<Features>]) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = Vec::new();
+
+    for preposition in 0..tokens.len() {
+        let readings = &features[preposition];
+        if readings.is_empty()
+            || !readings
+                .iter()
+                .all(|reading| reading.pos == PartOfSpeech::Preposition)
+        {
+            continue;
+        }
+
+        let mut governed_cases: Vec<&String> = Vec::new();
+        for case in readings.iter().flat_map(|reading| &reading.governed_cases) {
+            if !governed_cases.contains(&case) {
+                governed_cases.push(case);
+            }
+        }
+        if governed_cases.is_empty() {
+            continue;
+        }
+
+        // the object is the first word after the preposition that has a case
+        let last = (preposition + PREPOSITION_REACH).min(tokens.len() - 1);
+        let object = match (preposition + 1..=last)
+            .find(|&word| features[word].iter().any(|reading| reading.case.is_some()))
+        {
+            Some(object) => object,
+            None => continue,
+        };
+
+        let object_cases = unique_keys(features[object].iter().map(|reading| &reading.case));
+        if object_cases
+            .iter()
+            .any(|case| governed_cases.contains(&case))
+        {
+            continue;
+        }
+
+        issues.push(Issue {
+            kind: IssueKind::PrepositionCase,
+            words: vec![
+                tokens[preposition].text.clone(),
+                tokens[object].text.clone(),
+            ],
+            span: tokens[preposition].span.to(&tokens[object].span),
+            message: format!(
+                "'{}' takes the {}, but '{}' can only be {}",
+                tokens[preposition].text,
+                join_names(governed_cases.into_iter().cloned().collect(), case_name),
+                tokens[object].text,
+                join_names(object_cases, case_name)
+            ),
+        });
+    }
+
+    issues
+}
+
+fn check_agreement(tokens: &[Token], features: &[Vec<Features>]) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = Vec::new();
+    let only = |word: usize, pos: PartOfSpeech| {
+        !features[word].is_empty() && features[word].iter().all(|reading| reading.pos == pos)
+    };
+
+    for first in 0..tokens.len().saturating_sub(1) {
+        let second = first + 1;
+        let (adjective, noun) =
+            if only(first, PartOfSpeech::Adjective) && only(second, PartOfSpeech::Noun) {
+                (first, second)
+            } else if only(first, PartOfSpeech::Noun) && only(second, PartOfSpeech::Adjective) {
+                (second, first)
+            } else {
+                continue;
+            };
+
+        let pairs: Vec<(&Features, &Features)> = features[adjective]
+            .iter()
+            .flat_map(|adjective| features[noun].iter().map(move |noun| (adjective, noun)))
+            .collect();
+        if pairs
+            .iter()
+            .any(|(adjective, noun)| agrees(adjective, noun))
+        {
+            continue;
+        }
+
+        // words that only share one feature, or none, are most likely not meant to go together
+        let closest = pairs
+            .iter()
+            .find(|(adjective, noun)| differences(adjective, noun).len() == 1);
+        let (adjective_features, noun_features) = match closest {
+            Some(pair) => pair,
+            None => continue,
+        };
+
+        issues.push(Issue {
+            kind: IssueKind::Agreement,
+            words: vec![tokens[first].text.clone(), tokens[second].text.clone()],
+            span: tokens[first].span.to(&tokens[second].span),
+            message: format!(
+                "'{}' does not agree with '{}' in {}: it is {}, '{}' is {}",
+                tokens[adjective].text,
+                tokens[noun].text,
+                differences(adjective_features, noun_features).join(" and "),
+                describe(adjective_features),
+                tokens[noun].text,
+                describe(noun_features)
+            ),
+        });
+    }
+
+    issues
+}
+
+fn check_subjects(tokens: &[Token], features: &[Vec<Features>]) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = Vec::new();
+    let is_nominative = |reading: &Features| {
+        reading.pos == PartOfSpeech::Noun && reading.case.as_deref() == Some("NOM")
+    };
+
+    for verb in 0..tokens.len() {
+        // only verbs in the 3rd person that can only be singular or only plural, nouns are never the subject of the others
+        let readings = &features[verb];
+        if readings.is_empty()
+            || !readings
+                .iter()
+                .all(|reading| reading.pos == PartOfSpeech::Verb && reading.person == Some(3))
+        {
+            continue;
+        }
+        let numbers = unique_keys(readings.iter().map(|reading| &reading.number));
+        let verb_number = match numbers.as_slice() {
+            [number] => number,
+            _ => continue,
+        };
+
+        let subjects: Vec<usize> = (0..tokens.len())
+            .filter(|&word| word != verb && features[word].iter().any(is_nominative))
+            .collect();
+        if are_coordinated(tokens, &subjects) {
+            continue;
+        }
+        let agrees_with_subject = subjects.iter().any(|&subject| {
+            features[subject].iter().any(|reading| {
+                is_nominative(reading)
+                    && reading
+                        .number
+                        .as_ref()
+                        .is_some_and(|number| number == verb_number || number == "X")
+            })
+        });
+
+        // without a nominative the subject is in the verb
+        let subject = match subjects.first() {
+            Some(subject) if !agrees_with_subject => *subject,
+            _ => continue,
+        };
+
+        let (first, last) = (subject.min(verb), subject.max(verb));
+        issues.push(Issue {
+            kind: IssueKind::SubjectVerb,
+            words: vec![tokens[first].text.clone(), tokens[last].text.clone()],
+            span: tokens[first].span.to(&tokens[last].span),
+            message: format!(
+                "'{}' is {}, but its subject '{}' is not",
+                tokens[verb].text,
+                number_name(verb_number),
+                tokens[subject].text
+            ),
+        });
+    }
+
+    issues
+}
+
+// whether two of the nominatives are joined by a conjunction, which makes them a plural subject
+fn are_coordinated(tokens: &[Token], subjects: &[usize]) -> bool {
+    subjects.windows(2).any(|pair| {
+        let (first, second) = (pair[0], pair[1]);
+        tokens[first + 1..second]
+            .iter()
+            .any(|token| CONJUNCTIONS.contains(&token.text.to_lowercase().as_str()))
+            || tokens[second].text.to_lowercase().ends_with("que")
+    })
+}
+
+// the features the adjective and the noun do not share
+fn differences(adjective: &Features, noun: &Features) -> Vec<&'static str> {
+    let same = |first: &Option<String>, second: &Option<String>| match (first, second) {
+        (Some(first), Some(second)) => first == second || first == "X" || second == "X",
+        _ => true,
+    };
+
+    let mut differences: Vec<&'static str> = Vec::new();
+    if !same(&adjective.case, &noun.case) {
+        differences.push("case");
+    }
+    if !same(&adjective.number, &noun.number) {
+        differences.push("number");
+    }
+    if !genders_agree(adjective.gender.as_deref(), noun.gender.as_deref()) {
+        differences.push("gender");
+    }
+
+    differences
+}
+
+// ex: accusative singular feminine
+fn describe(features: &Features) -> String {
+    let mut description: Vec<&str> = Vec::new();
+    if let Some(case) = &features.case {
+        description.push(case_name(case));
+    }
+    if let Some(number) = features.number.as_ref().filter(|number| *number != "X") {
+        description.push(number_name(number));
+    }
+    if let Some(gender) = features
+        .gender
+        .as_ref()
+        .filter(|gender| *gender != "X" && *gender != "C")
+    {
+        description.push(Gender::dict_key_to_gender(gender).as_str());
+    }
+
+    description.join(" ")
+}
+
+fn unique_keys<'a>(keys: impl Iterator<Item = &'a Option<String>>) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::new();
+    for key in keys.flatten() {
+        if !unique.contains(key) {
+            unique.push(key.clone());
+        }
+    }
+
+    unique
+}
+
+// ex: the accusative or ablative
+fn join_names(keys: Vec<String>, name: fn(&str) -> &'static str) -> String {
+    let names: Vec<&str> = keys.iter().map(|key| name(key)).collect();
+    names.join(" or ")
+}
@@ -0,0 +1,190 @@
+use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
+use crate::dictionary_structures::dictionary_values::{Form, Inflection};
+use crate::translators::latin_to_english::parser::parse;
+use crate::translators::latin_to_english::LatinTranslationInfo;
+
+// the shortest part a compound is split into, shorter parts match too many stems
+const MIN_PART_LENGTH: usize = 3;
+
+/**
+ * Finds words made of two words that both inflect, which the dictionary only has in the nominative.
+ * ex: reipublicae -> rei + publicae, iurisiurandi -> iuris + iurandi
+ * The parts have to agree in case and number, unless the first part does not inflect (an adverb, preposition or indeclinable word).
+ */
+pub fn find_compounds(latin_word: &str) -> Option<Vec<LatinTranslationInfo>> {
+    let mut output: Vec<LatinTranslationInfo> = Vec::new();
+
+    for (split, _) in latin_word.char_indices().skip(MIN_PART_LENGTH) {
+        let (first, second) = latin_word.split_at(split);
+        if second.chars().count() < MIN_PART_LENGTH {
+            break;
+        }
+
+        let first_words = match parse(first, true) {
+            Some(first_words) => first_words,
+            None => continue,
+        };
+        let second_words = match parse(second, true) {
+            Some(second_words) => second_words,
+            None => continue,
+        };
+
+        // parts only found with a trick are too likely to be wrong
+        for first_word in first_words.iter().filter(|word| word.tricks.is_none()) {
+            for second_word in second_words.iter().filter(|word| word.tricks.is_none()) {
+                if let Some(compound) = combine(first, first_word, second_word) {
+                    let already_found = output
+                        .iter()
+                        .any(|word| word.word.orth == compound.word.orth);
+
+                    if !already_found {
+                        output.push(compound);
+                    }
+                }
+            }
+        }
+    }
+
+    if output.is_empty() {
+        None
+    } else {
+        Some(output)
+    }
+}
+
+/**
+ * Makes one word of the two parts, with the meanings of both and the inflections the parts agree on.
+ * Gives nothing if the parts can not be used together.
+ */
+fn combine(
+    first_surface: &str,
+    first: &LatinTranslationInfo,
+    second: &LatinTranslationInfo,
+) -> Option<LatinTranslationInfo> {
+    let mut first_part = first.clone();
+    let mut second_part = second.clone();
+
+    if !is_invariant(first) {
+        let first_forms = case_and_number(first);
+        let agreeing: Vec<(String, String)> = case_and_number(second)
+            .into_iter()
+            .filter(|form| {
+                first_forms
+                    .iter()
+                    .any(|first_form| agrees(first_form, form))
+            })
+            .collect();
+
+        if agreeing.is_empty() {
+            return None;
+        }
+
+        keep_agreeing_inflections(&mut first_part, &agreeing);
+        keep_agreeing_inflections(&mut second_part, &agreeing);
+    }
+
+    let mut compound = LatinTranslationInfo::new();
+    compound.word = second.word.clone();
+    compound.word.orth = format!("{} {}", first.word.orth, second.word.orth);
+    compound.word.senses = vec![format!(
+        "{} + {}",
+        first.word.senses.first().map_or("", |sense| sense.as_str()),
+        second
+            .word
+            .senses
+            .first()
+            .map_or("", |sense| sense.as_str())
+    )];
+    // the compound is not an entry in the dictionary, so it has no principle parts or id
+    compound.word.parts = Vec::new();
+    compound.word.id = 0;
+    compound.word.modifiers = None;
+    compound.word.extension_senses = None;
+
+    compound.stem = second.stem.clone();
+    compound.stem.orth = format!("{first_surface}{}", second.stem.orth);
+    compound.stem.wid = 0;
+    compound.inflections = second_part.inflections.clone();
+    compound.derived = second.derived.clone();
+    compound.compound = Some(vec![first_part, second_part]);
+
+    Some(compound)
+}
+
+// words that are used in compounds without changing their ending
+fn is_invariant(word: &LatinTranslationInfo) -> bool {
+    let indeclinable = word
+        .word
+        .n
+        .as_ref()
+        .and_then(|n| n.first())
+        .is_some_and(|n| n.get_n_value_1() == 9);
+
+    indeclinable
+        || matches!(
+            word.get_pos(),
+            PartOfSpeech::Adverb
+                | PartOfSpeech::Preposition
+                | PartOfSpeech::Conjunction
+                | PartOfSpeech::Interjection
+        )
+}
+
+/**
+ * Every case and number the word could be in, from its inflections or from the form of a unique word.
+ */
+fn case_and_number(word: &LatinTranslationInfo) -> Vec<(String, String)> {
+    let pos = word.get_pos();
+    if !is_declined(pos) {
+        return Vec::new();
+    }
+
+    match &word.inflections {
+        Some(inflections) => inflections
+            .iter()
+            .filter(|inflection| inflection.pos == pos)
+            .filter_map(|inflection| form_case_and_number(&inflection.form))
+            .collect(),
+        None => form_case_and_number(&word.word.form).into_iter().collect(),
+    }
+}
+
+fn keep_agreeing_inflections(word: &mut LatinTranslationInfo, agreeing: &[(String, String)]) {
+    let pos = word.get_pos();
+
+    if let Some(inflections) = &mut word.inflections {
+        inflections.retain(|inflection: &Inflection| {
+            inflection.pos == pos
+                && form_case_and_number(&inflection.form)
+                    .is_some_and(|form| agreeing.iter().any(|other| agrees(&form, other)))
+        });
+    }
+}
+
+// an inflection form starts with the case and number for declined words, ex: "GEN S C"
+fn form_case_and_number(form: &Form) -> Option<(String, String)> {
+    match form {
+        Form::StrForm(form) => {
+            let mut tokens = form.split_whitespace();
+            Some((tokens.next()?.to_string(), tokens.next()?.to_string()))
+        }
+        Form::LongForm(_) => None,
+    }
+}
+
+// X is used for any case or number
+fn agrees(first: &(String, String), second: &(String, String)) -> bool {
+    let fits = |a: &str, b: &str| a == b || a == "X" || b == "X";
+    fits(&first.0, &second.0) && fits(&first.1, &second.1)
+}
+
+fn is_declined(pos: PartOfSpeech) -> bool {
+    matches!(
+        pos,
+        PartOfSpeech::Noun
+            | PartOfSpeech::Pronoun
+            | PartOfSpeech::Adjective
+            | PartOfSpeech::Numeral
+            | PartOfSpeech::Participle
+    )
+}
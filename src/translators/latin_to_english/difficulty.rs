@@ -0,0 +1,236 @@
+use crate::dictionary_structures::dictionary_keys::{Age, Frequency};
+use crate::translators::latin_to_english::frequency::UnrecognizedToken;
+use crate::translators::latin_to_english::glossary::is_known;
+use crate::translators::latin_to_english::lemmatizer::LemmatizedToken;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+// the frequency classes of the dictionary, from the most frequent to the rarest
+const FREQUENCY_CLASSES: [Frequency; 6] = [
+    Frequency::VeryFrequent,
+    Frequency::Frequent,
+    Frequency::Common,
+    Frequency::Lesser,
+    Frequency::Uncommon,
+    Frequency::VeryRare,
+];
+
+/**
+ * How many words of a text have lemmas of a frequency class, and how many have lemmas of this class or one before it.
+ * Percentages are of all the words of the text.
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FrequencyShare {
+    pub frequency: Frequency,
+    pub tokens: usize,
+    pub percent: f64,
+    pub cumulative_percent: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct AgeShare {
+    pub age: Age,
+    pub tokens: usize,
+    pub percent: f64,
+}
+
+/**
+ * How hard a text is to read: how much of it a list of known words covers, how frequent and how late its vocabulary is,
+ * and the words that were not found. Percentages are of all the words of the text, each word counted by its best lemma.
+ * `known_percent` is only there when a list of known words is given.
+ * `frequencies` has the six classes from very frequent to very rare, then any other frequency the lemmas have,
+ * so the last `cumulative_percent` is the share of the words that were recognized.
+ * Rare words are uncommon or rarer, medieval words are from the medieval, scholar or modern ages.
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DifficultyReport {
+    pub tokens: usize,
+    pub recognized: usize,
+    pub distinct_lemmas: usize,
+    pub known_percent: Option<f64>,
+    pub frequencies: Vec<FrequencyShare>,
+    pub ages: Vec<AgeShare>,
+    pub rare_percent: f64,
+    pub medieval_percent: f64,
+    pub unknown_percent: f64,
+    pub unknown_words: Vec<UnrecognizedToken>,
+}
+
+impl DifficultyReport {
+    /**
+     * Makes the report from the lemmatized words of a text.
+     * `known_words` are headwords in lower case (see `is_known`).
+     */
+    pub fn new(
+        lemmatized_tokens: &[LemmatizedToken],
+        known_words: Option<&HashSet<String>>,
+    ) -> DifficultyReport {
+        let tokens = lemmatized_tokens.len();
+        let mut lemmas: HashSet<(i32, &str)> = HashSet::new();
+        let mut frequencies: HashMap<Frequency, usize> = HashMap::new();
+        let mut ages: HashMap<Age, usize> = HashMap::new();
+        let mut unknown: HashMap<String, usize> = HashMap::new();
+        let mut known = 0;
+
+        for token in lemmatized_tokens {
+            let form = token.token.to_lowercase();
+            let lemma = match &token.lemma {
+                Some(lemma) => lemma,
+                None => {
+                    *unknown.entry(form).or_insert(0) += 1;
+                    continue;
+                }
+            };
+
+            lemmas.insert((lemma.id, lemma.headword.as_str()));
+            *frequencies.entry(lemma.frequency).or_insert(0) += 1;
+            *ages.entry(lemma.age).or_insert(0) += 1;
+
+            if known_words.is_some_and(|known_words| is_known(known_words, &lemma.headword, &form))
+            {
+                known += 1;
+            }
+        }
+
+        let percent = |count: usize| {
+            if tokens == 0 {
+                0.0
+            } else {
+                count as f64 * 100.0 / tokens as f64
+            }
+        };
+
+        // the other values (inscription, graffiti, Pliny, all or none, unknown) come after the classes, when a lemma has them,
+        // so that every recognized word is in one of the shares
+        let mut other_frequencies: Vec<Frequency> = frequencies
+            .keys()
+            .filter(|frequency| !FREQUENCY_CLASSES.contains(frequency))
+            .copied()
+            .collect();
+        other_frequencies.sort_by_key(|frequency| frequency.as_number());
+
+        let mut covered = 0;
+        let frequency_shares: Vec<FrequencyShare> = FREQUENCY_CLASSES
+            .iter()
+            .chain(other_frequencies.iter())
+            .map(|frequency| {
+                let count = frequencies.get(frequency).copied().unwrap_or(0);
+                covered += count;
+                FrequencyShare {
+                    frequency: *frequency,
+                    tokens: count,
+                    percent: percent(count),
+                    cumulative_percent: percent(covered),
+                }
+            })
+            .collect();
+
+        let mut age_shares: Vec<AgeShare> = ages
+            .iter()
+            .map(|(age, count)| AgeShare {
+                age: *age,
+                tokens: *count,
+                percent: percent(*count),
+            })
+            .collect();
+        age_shares.sort_by(|first, second| {
+            second
+                .tokens
+                .cmp(&first.tokens)
+                .then_with(|| first.age.as_str().cmp(second.age.as_str()))
+        });
+
+        let rare = frequencies
+            .iter()
+            .filter(|(frequency, _)| is_rare(**frequency))
+            .map(|(_, count)| count)
+            .sum();
+        let medieval = ages
+            .iter()
+            .filter(|(age, _)| matches!(age, Age::Medieval | Age::Scholar | Age::Modern))
+            .map(|(_, count)| count)
+            .sum();
+
+        let mut unknown_words: Vec<UnrecognizedToken> = unknown
+            .into_iter()
+            .map(|(token, count)| UnrecognizedToken { token, count })
+            .collect();
+        unknown_words.sort_by(|first, second| {
+            second
+                .count
+                .cmp(&first.count)
+                .then_with(|| first.token.cmp(&second.token))
+        });
+        let unknown_tokens: usize = unknown_words.iter().map(|word| word.count).sum();
+
+        DifficultyReport {
+            tokens,
+            recognized: tokens - unknown_tokens,
+            distinct_lemmas: lemmas.len(),
+            known_percent: known_words.map(|_| percent(known)),
+            frequencies: frequency_shares,
+            ages: age_shares,
+            rare_percent: percent(rare),
+            medieval_percent: percent(medieval),
+            unknown_percent: percent(unknown_tokens),
+            unknown_words,
+        }
+    }
+}
+
+// uncommon or rarer, words only found in inscriptions, graffiti or Pliny included
+fn is_rare(frequency: Frequency) -> bool {
+    matches!(
+        frequency,
+        Frequency::Uncommon
+            | Frequency::VeryRare
+            | Frequency::Inscription
+            | Frequency::Graffiti
+            | Frequency::Pliny
+    )
+}
+
+impl Display for DifficultyReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} words, {} recognized, {} distinct lemmas",
+            self.tokens, self.recognized, self.distinct_lemmas
+        )?;
+        if let Some(known_percent) = self.known_percent {
+            writeln!(f, "known words: {known_percent:.1}%")?;
+        }
+
+        writeln!(f, "\nfrequency (words, up to this class):")?;
+        for share in &self.frequencies {
+            writeln!(
+                f,
+                "  {:<14} {:>5.1}%  {:>5.1}%",
+                share.frequency.as_str(),
+                share.percent,
+                share.cumulative_percent
+            )?;
+        }
+
+        writeln!(f, "\nage:")?;
+        for share in &self.ages {
+            writeln!(f, "  {:<20} {:>5.1}%", share.age.as_str(), share.percent)?;
+        }
+
+        writeln!(f, "\nrare words: {:.1}%", self.rare_percent)?;
+        writeln!(f, "medieval words: {:.1}%", self.medieval_percent)?;
+        write!(f, "unknown words: {:.1}%", self.unknown_percent)?;
+
+        let unknown_words: Vec<String> = self
+            .unknown_words
+            .iter()
+            .map(|word| format!("{} ({})", word.token, word.count))
+            .collect();
+        if !unknown_words.is_empty() {
+            write!(f, "\n  {}", unknown_words.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,476 @@
+use crate::dictionary_structures::dictionary_keys::{Declension, Gender, Number, PartOfSpeech};
+use crate::dictionary_structures::dictionary_values::Form;
+use crate::translators::latin_to_english::LatinTranslationInfo;
+use crate::translators::DisplayType;
+use crate::utils::ocr::Correction;
+use crate::utils::tokenizer::SourceSpan;
+use serde::{Deserialize, Serialize};
+
+// a preposition says more about the case of the next word than agreement does
+const GOVERNED_CASE_SCORE: i32 = 2;
+const AGREEMENT_SCORE: i32 = 1;
+const SUBJECT_SCORE: i32 = 1;
+// how many words after a preposition its object can be, ex: in magna urbe
+pub const PREPOSITION_REACH: usize = 2;
+const GOVERNED_CASES: [&str; 3] = ["GEN", "ACC", "ABL"];
+
+/**
+ * One way to read a word: one of its definitions with one of its inflections,
+ * how much the words around it support it, and why.
+ */
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Reading {
+    pub definition: LatinTranslationInfo,
+    pub score: i32,
+    pub reasons: Vec<String>,
+}
+
+impl Reading {
+    fn new(definition: LatinTranslationInfo) -> Reading {
+        Reading {
+            definition,
+            score: 0,
+            reasons: Vec::new(),
+        }
+    }
+
+    fn support(&mut self, score: i32, reason: String) {
+        self.score += score;
+        self.reasons.push(reason);
+    }
+}
+
+/**
+ * A word of a sentence and its readings, the best first.
+ */
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WordAnalysis {
+    pub word: String,
+    pub normalized: String,
+    pub span: SourceSpan,
+    pub readings: Vec<Reading>,
+    // changes made to the word when cleaning scanned text
+    pub corrections: Option<Vec<Correction>>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SentenceAnalysis {
+    pub text: String,
+    pub span: SourceSpan,
+    pub words: Vec<WordAnalysis>,
+}
+
+impl SentenceAnalysis {
+    pub fn display(&self, display_type: DisplayType) {
+        match display_type {
+            DisplayType::Pretty(detailed) => {
+                println!("{}", self.text);
+
+                for word in &self.words {
+                    println!("\n{}", word.word);
+                    if let Some(corrections) = &word.corrections {
+                        for correction in corrections {
+                            println!("  {correction}");
+                        }
+                    }
+                    if word.readings.is_empty() {
+                        println!("  No definitions found");
+                    }
+
+                    for reading in &word.readings {
+                        let definition = &reading.definition;
+                        let form = match definition.inflections.as_ref().and_then(|i| i.first()) {
+                            Some(inflection) => match &inflection.form {
+                                Form::StrForm(form) => form.clone(),
+                                Form::LongForm(form) => form.as_clean_str(),
+                            },
+                            None => String::new(),
+                        };
+
+                        println!(
+                            "  {} | {} {form} | {}",
+                            definition.word.orth,
+                            definition.get_pos().as_str(),
+                            reading.score
+                        );
+                        if detailed {
+                            println!("    {}", definition.word.senses.join(" "));
+                        }
+                        for reason in &reading.reasons {
+                            println!("    + {reason}");
+                        }
+                    }
+                }
+
+                println!();
+            }
+            DisplayType::Json => {
+                println!("{}", serde_json::to_string_pretty(&self).unwrap());
+            }
+        }
+    }
+}
+
+/**
+ * What the inflection of a reading says about it, as the keys of the inflection data (ABL, P, F).
+ */
+pub struct Features {
+    pub pos: PartOfSpeech,
+    pub case: Option<String>,
+    pub number: Option<String>,
+    pub gender: Option<String>,
+    pub person: Option<i8>,
+    pub governed_cases: Vec<String>,
+}
+
+/**
+ * Ranks the readings of the words of a sentence by how well they fit the words around them.
+ * `words` are the words as they are written, `definitions` what was found for each of them.
+ * A reading is moved up when:
+ * - it is in the case a preposition just before it takes, or is the preposition taking the case of the word after it
+ * - it agrees in case, number and gender with the noun or adjective next to it
+ * - it is a verb agreeing in number with a nominative in the sentence, or that nominative
+ *
+ * Readings that are not supported keep the order they were found in.
+ */
+pub fn rank_readings(
+    words: &[String],
+    definitions: &[Vec<LatinTranslationInfo>],
+) -> Vec<Vec<Reading>> {
+    let mut readings: Vec<Vec<Reading>> = definitions
+        .iter()
+        .map(|word_definitions| split_readings(word_definitions))
+        .collect();
+    let features: Vec<Vec<Features>> = readings
+        .iter()
+        .map(|word_readings| {
+            word_readings
+                .iter()
+                .map(|reading| get_features(&reading.definition))
+                .collect()
+        })
+        .collect();
+
+    prefer_governed_cases(words, &features, &mut readings);
+    prefer_agreement(words, &features, &mut readings);
+    prefer_subjects(words, &features, &mut readings);
+
+    for word_readings in readings.iter_mut() {
+        word_readings.sort_by_key(|reading| std::cmp::Reverse(reading.score));
+    }
+
+    readings
+}
+
+fn split_readings(definitions: &[LatinTranslationInfo]) -> Vec<Reading> {
+    let mut readings: Vec<Reading> = Vec::new();
+
+    for definition in definitions {
+        let inflections = match &definition.inflections {
+            Some(inflections) if !inflections.is_empty() => inflections,
+            _ => {
+                readings.push(Reading::new(definition.clone()));
+                continue;
+            }
+        };
+
+        for inflection in inflections {
+            let mut reading = definition.clone();
+            reading.set_inflections(vec![inflection.clone()]);
+            readings.push(Reading::new(reading));
+        }
+    }
+
+    readings
+}
+
+/**
+ * The features of every reading of a word, one for each definition and inflection.
+ */
+pub fn word_features(definitions: &[LatinTranslationInfo]) -> Vec<Features> {
+    split_readings(definitions)
+        .iter()
+        .map(|reading| get_features(&reading.definition))
+        .collect()
+}
+
+fn get_features(definition: &LatinTranslationInfo) -> Features {
+    let inflection = definition
+        .inflections
+        .as_ref()
+        .and_then(|inflections| inflections.first());
+    let pos = inflection.map_or(definition.get_pos(), |inflection| inflection.pos);
+    let form: Vec<&str> = match inflection.map(|inflection| &inflection.form) {
+        Some(Form::StrForm(form)) => form.split_whitespace().collect(),
+        _ => Vec::new(),
+    };
+    let word_form: Vec<&str> = match &definition.word.form {
+        Form::StrForm(form) => form.split_whitespace().collect(),
+        Form::LongForm(_) => Vec::new(),
+    };
+    let key = |index: usize, form: &[&str]| form.get(index).map(|key| key.to_string());
+
+    let mut features = Features {
+        pos,
+        case: None,
+        number: None,
+        gender: None,
+        person: None,
+        governed_cases: Vec::new(),
+    };
+
+    match pos {
+        PartOfSpeech::Noun
+        | PartOfSpeech::Pronoun
+        | PartOfSpeech::Adjective
+        | PartOfSpeech::Numeral
+        | PartOfSpeech::Participle
+        | PartOfSpeech::Supine => {
+            features.case = key(0, &form);
+            features.number = key(1, &form);
+            features.gender = key(2, &form);
+
+            // noun endings are often shared by genders (C, X), the noun itself has one (1 1 F T)
+            if pos == PartOfSpeech::Noun {
+                if let Some(gender) = word_form
+                    .get(2)
+                    .filter(|gender| ["M", "F", "N", "C"].contains(gender))
+                {
+                    features.gender = Some(gender.to_string());
+                }
+            }
+        }
+        // PRES ACTIVE IND 3 S, infinitives have no person
+        PartOfSpeech::Verb if form.len() == 5 => {
+            features.person = form[3].parse::<i8>().ok().filter(|person| *person > 0);
+            features.number = key(4, &form);
+        }
+        PartOfSpeech::Preposition => {
+            // the case is in the inflection (ACC 1 0) or in the form of the word
+            features.governed_cases = form
+                .iter()
+                .take(1)
+                .chain(word_form.iter())
+                .filter(|key| GOVERNED_CASES.contains(key))
+                .map(|key| key.to_string())
+                .collect();
+        }
+        _ => {}
+    }
+
+    features
+}
+
+fn prefer_governed_cases(
+    words: &[String],
+    features: &[Vec<Features>],
+    readings: &mut [Vec<Reading>],
+) {
+    let governs = |preposition: &Features, object: &Features| {
+        object
+            .case
+            .as_ref()
+            .is_some_and(|case| preposition.governed_cases.contains(case))
+    };
+
+    for preposition in 0..words.len() {
+        let last = (preposition + PREPOSITION_REACH).min(words.len() - 1);
+
+        for object in preposition + 1..=last {
+            let found = support_fitting(
+                readings,
+                features,
+                (object, preposition),
+                GOVERNED_CASE_SCORE,
+                |object, preposition| governs(preposition, object),
+                |object_features, _| {
+                    format!(
+                        "{} after the preposition '{}'",
+                        case_name(object_features.case.as_deref().unwrap_or_default()),
+                        words[preposition]
+                    )
+                },
+            );
+            support_fitting(
+                readings,
+                features,
+                (preposition, object),
+                GOVERNED_CASE_SCORE,
+                governs,
+                |_, object_features| {
+                    format!(
+                        "takes the {} of '{}'",
+                        case_name(object_features.case.as_deref().unwrap_or_default()),
+                        words[object]
+                    )
+                },
+            );
+
+            // the first word in a case the preposition takes is its object
+            if found {
+                break;
+            }
+        }
+    }
+}
+
+fn prefer_agreement(words: &[String], features: &[Vec<Features>], readings: &mut [Vec<Reading>]) {
+    let fits = |first: &Features, second: &Features| {
+        is_noun_and_modifier(first, second) && agrees(first, second)
+    };
+
+    for first in 0..words.len().saturating_sub(1) {
+        let second = first + 1;
+
+        for (word, other) in [(first, second), (second, first)] {
+            support_fitting(
+                readings,
+                features,
+                (word, other),
+                AGREEMENT_SCORE,
+                fits,
+                |word_features, other_features| {
+                    format!(
+                        "agrees with '{}' ({})",
+                        words[other],
+                        describe_agreement(word_features, other_features)
+                    )
+                },
+            );
+        }
+    }
+}
+
+fn prefer_subjects(words: &[String], features: &[Vec<Features>], readings: &mut [Vec<Reading>]) {
+    // nouns are always in the 3rd person, the 1st and 2nd need a pronoun the ending does not show
+    let fits = |verb: &Features, subject: &Features| {
+        verb.pos == PartOfSpeech::Verb
+            && verb.person == Some(3)
+            && subject.pos == PartOfSpeech::Noun
+            && subject.case.as_deref() == Some("NOM")
+            && verb.number.is_some()
+            && subject.number == verb.number
+    };
+
+    for verb in 0..words.len() {
+        for subject in (0..words.len()).filter(|subject| *subject != verb) {
+            support_fitting(
+                readings,
+                features,
+                (verb, subject),
+                SUBJECT_SCORE,
+                fits,
+                |verb_features, _| {
+                    format!(
+                        "agrees with the subject '{}' ({})",
+                        words[subject],
+                        number_name(verb_features.number.as_deref().unwrap_or_default())
+                    )
+                },
+            );
+            support_fitting(
+                readings,
+                features,
+                (subject, verb),
+                SUBJECT_SCORE,
+                |subject, verb| fits(verb, subject),
+                |_, _| format!("can be the subject of '{}'", words[verb]),
+            );
+        }
+    }
+}
+
+/**
+ * Moves up each reading of a word that fits a reading of the other word, once however many readings of the other word it fits.
+ * Returns whether any reading fits.
+ */
+fn support_fitting(
+    readings: &mut [Vec<Reading>],
+    features: &[Vec<Features>],
+    (word, other): (usize, usize),
+    score: i32,
+    fits: impl Fn(&Features, &Features) -> bool,
+    reason: impl Fn(&Features, &Features) -> String,
+) -> bool {
+    let mut found = false;
+
+    for (reading, word_features) in features[word].iter().enumerate() {
+        let other_features = features[other]
+            .iter()
+            .find(|other_features| fits(word_features, other_features));
+
+        if let Some(other_features) = other_features {
+            found = true;
+            readings[word][reading].support(score, reason(word_features, other_features));
+        }
+    }
+
+    found
+}
+
+fn is_noun_and_modifier(first: &Features, second: &Features) -> bool {
+    let is_modifier = |features: &Features| {
+        matches!(
+            features.pos,
+            PartOfSpeech::Adjective
+                | PartOfSpeech::Participle
+                | PartOfSpeech::Pronoun
+                | PartOfSpeech::Numeral
+        )
+    };
+
+    (first.pos == PartOfSpeech::Noun && is_modifier(second))
+        || (is_modifier(first) && second.pos == PartOfSpeech::Noun)
+}
+
+pub fn agrees(first: &Features, second: &Features) -> bool {
+    let same = |first: &Option<String>, second: &Option<String>| match (first, second) {
+        (Some(first), Some(second)) => first == second || first == "X" || second == "X",
+        _ => false,
+    };
+
+    same(&first.case, &second.case)
+        && same(&first.number, &second.number)
+        && genders_agree(first.gender.as_deref(), second.gender.as_deref())
+}
+
+// C is masculine or feminine, X is any gender
+pub fn genders_agree(first: Option<&str>, second: Option<&str>) -> bool {
+    match (first.unwrap_or("X"), second.unwrap_or("X")) {
+        ("X", _) | (_, "X") => true,
+        ("C", other) | (other, "C") => other != "N",
+        (first, second) => first == second,
+    }
+}
+
+fn describe_agreement(first: &Features, second: &Features) -> String {
+    let pick = |first: &Option<String>, second: &Option<String>| match (
+        first.as_deref(),
+        second.as_deref(),
+    ) {
+        (Some("X") | Some("C") | None, Some(second)) => second.to_string(),
+        (Some(first), _) => first.to_string(),
+        (None, None) => String::new(),
+    };
+
+    let gender = pick(&first.gender, &second.gender);
+    let mut description = format!(
+        "{} {}",
+        case_name(&pick(&first.case, &second.case)),
+        number_name(&pick(&first.number, &second.number))
+    );
+    if gender != "X" && gender != "C" {
+        description.push(' ');
+        description.push_str(Gender::dict_key_to_gender(&gender).as_str());
+    }
+
+    description
+}
+
+pub fn case_name(case: &str) -> &'static str {
+    Declension::dict_key_to_declension(case).as_str()
+}
+
+pub fn number_name(number: &str) -> &'static str {
+    Number::dict_key_to_number(number).as_str()
+}
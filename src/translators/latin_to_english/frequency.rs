@@ -0,0 +1,191 @@
+use crate::dictionary_structures::dictionary_keys::{Frequency, PartOfSpeech};
+use crate::translators::latin_to_english::lemmatizer::LemmatizedToken;
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+
+pub const CSV_HEADER: &str = "word,headword,id,pos,frequency,count,forms";
+
+/**
+ * What the words of a corpus are counted by: the dictionary entry they are a form of, or how they are written.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeBy {
+    Lemma,
+    Surface,
+}
+
+impl FromStr for MergeBy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lemma" => Ok(MergeBy::Lemma),
+            "surface" => Ok(MergeBy::Surface),
+            _ => Err(Error::InvalidMergeBy(s.to_string())),
+        }
+    }
+}
+
+/**
+ * How often a lemma, or a form, is found in the corpus.
+ * `frequency` is how frequent the dictionary says the lemma is, `forms` are the forms of a lemma that were found.
+ * When counting by form, `headword`, `id`, `pos` and `frequency` are the ones of the best lemma of the form.
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FrequencyEntry {
+    pub word: String,
+    pub headword: String,
+    pub id: i32,
+    pub pos: PartOfSpeech,
+    pub frequency: Frequency,
+    pub count: usize,
+    pub forms: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct UnrecognizedToken {
+    pub token: String,
+    pub count: usize,
+}
+
+/**
+ * The lemmas or forms of a corpus, the most frequent first, and the words that were not found.
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FrequencyList {
+    pub files: Vec<String>,
+    pub merge_by: MergeBy,
+    pub tokens: usize,
+    pub unrecognized: usize,
+    pub entries: Vec<FrequencyEntry>,
+    pub unrecognized_tokens: Vec<UnrecognizedToken>,
+}
+
+impl FrequencyList {
+    /**
+     * Counts the lemmatized words of each file, words are counted in lower case.
+     * Only the best lemma of each word is counted.
+     */
+    pub fn new(files: Vec<(String, Vec<LemmatizedToken>)>, merge_by: MergeBy) -> FrequencyList {
+        let mut entries: Vec<FrequencyEntry> = Vec::new();
+        let mut entry_indexes: HashMap<String, usize> = HashMap::new();
+        let mut unrecognized: HashMap<String, usize> = HashMap::new();
+        let mut tokens = 0;
+        let mut file_names: Vec<String> = Vec::new();
+
+        for (file, lemmatized_tokens) in files {
+            file_names.push(file);
+
+            for token in lemmatized_tokens {
+                tokens += 1;
+                let form = token.token.to_lowercase();
+
+                let lemma = match token.lemma {
+                    Some(lemma) => lemma,
+                    None => {
+                        *unrecognized.entry(form).or_insert(0) += 1;
+                        continue;
+                    }
+                };
+
+                let key = match merge_by {
+                    MergeBy::Lemma => format!("{} {}", lemma.id, lemma.headword),
+                    MergeBy::Surface => form.clone(),
+                };
+                let index = *entry_indexes.entry(key).or_insert_with(|| {
+                    entries.push(FrequencyEntry {
+                        word: match merge_by {
+                            MergeBy::Lemma => lemma.headword.clone(),
+                            MergeBy::Surface => form.clone(),
+                        },
+                        headword: lemma.headword.clone(),
+                        id: lemma.id,
+                        pos: lemma.pos,
+                        frequency: lemma.frequency,
+                        count: 0,
+                        forms: Vec::new(),
+                    });
+                    entries.len() - 1
+                });
+
+                let entry = &mut entries[index];
+                entry.count += 1;
+                if merge_by == MergeBy::Lemma && !entry.forms.contains(&form) {
+                    entry.forms.push(form);
+                }
+            }
+        }
+
+        // the most frequent first, then in alphabetical order so the list is the same every time
+        entries.sort_by(|first, second| {
+            second
+                .count
+                .cmp(&first.count)
+                .then_with(|| first.word.cmp(&second.word))
+        });
+        let mut unrecognized_tokens: Vec<UnrecognizedToken> = unrecognized
+            .into_iter()
+            .map(|(token, count)| UnrecognizedToken { token, count })
+            .collect();
+        unrecognized_tokens.sort_by(|first, second| {
+            second
+                .count
+                .cmp(&first.count)
+                .then_with(|| first.token.cmp(&second.token))
+        });
+
+        FrequencyList {
+            files: file_names,
+            merge_by,
+            tokens,
+            unrecognized: unrecognized_tokens.iter().map(|token| token.count).sum(),
+            entries,
+            unrecognized_tokens,
+        }
+    }
+
+    /**
+     * The list as comma separated values, with a header line, in the order of `CSV_HEADER`.
+     * The unrecognized words come after the others, with only their word and count, and `unrecognized` as their pos.
+     */
+    pub fn as_csv(&self) -> String {
+        let mut lines: Vec<String> = vec![CSV_HEADER.to_string()];
+
+        for entry in &self.entries {
+            lines.push(
+                [
+                    csv_field(&entry.word),
+                    csv_field(&entry.headword),
+                    entry.id.to_string(),
+                    csv_field(entry.pos.as_str()),
+                    csv_field(entry.frequency.as_str()),
+                    entry.count.to_string(),
+                    csv_field(&entry.forms.join(" ")),
+                ]
+                .join(","),
+            );
+        }
+
+        for token in &self.unrecognized_tokens {
+            lines.push(format!(
+                "{},,,unrecognized,,{},",
+                csv_field(&token.token),
+                token.count
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+// fields with commas, quotes or line breaks are quoted, with their quotes doubled
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
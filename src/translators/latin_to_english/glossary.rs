@@ -0,0 +1,256 @@
+use crate::dictionary_structures::dictionary_keys::{Gender, PartOfSpeech};
+use crate::dictionary_structures::dictionary_values::Form;
+use crate::translators::latin_to_english::LatinTranslationInfo;
+use crate::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GlossaryFormat {
+    Markdown,
+    Latex,
+    Html,
+}
+
+impl FromStr for GlossaryFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" | "md" => Ok(GlossaryFormat::Markdown),
+            "latex" | "tex" => Ok(GlossaryFormat::Latex),
+            "html" => Ok(GlossaryFormat::Html),
+            _ => Err(Error::InvalidGlossaryFormat(s.to_string())),
+        }
+    }
+}
+
+/**
+ * A lemma of a passage as it is given in a glossary.
+ * ex: puella, puellae | f., 1st declension | girl | lines 1, 4
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct GlossaryEntry {
+    pub id: i32,
+    pub headword: String,
+    pub dictionary_form: String,
+    pub grammar: String,
+    pub sense: String,
+    pub lines: Vec<usize>,
+}
+
+/**
+ * The lemmas of a passage a reader does not know, in the order they first appear in.
+ */
+#[derive(Debug, Clone, PartialEq, Default, Deserialize, Serialize)]
+pub struct Glossary {
+    pub entries: Vec<GlossaryEntry>,
+}
+
+impl Glossary {
+    pub fn new() -> Glossary {
+        Glossary {
+            entries: Vec::new(),
+        }
+    }
+
+    /**
+     * Adds the line a lemma is found on, adding the lemma if it is the first time it is found.
+     * The definition has to have been processed for the output, so that it has its principal parts.
+     * Lemmas in `known_words` are left out (see `is_known`).
+     */
+    pub fn add(
+        &mut self,
+        definition: &LatinTranslationInfo,
+        line: usize,
+        known_words: &HashSet<String>,
+    ) {
+        let word = &definition.word;
+        let headword = word.parts.first().unwrap_or(&word.orth).clone();
+        if is_known(known_words, &headword, &word.orth) {
+            return;
+        }
+
+        let position = self
+            .entries
+            .iter()
+            .position(|entry| entry.id == word.id && entry.headword == headword);
+        let entry = match position {
+            Some(position) => &mut self.entries[position],
+            None => {
+                self.entries.push(GlossaryEntry {
+                    id: word.id,
+                    dictionary_form: if word.parts.is_empty() {
+                        word.orth.clone()
+                    } else {
+                        word.parts.join(", ")
+                    },
+                    headword,
+                    grammar: describe_grammar(definition),
+                    sense: short_sense(&word.senses),
+                    lines: Vec::new(),
+                });
+                self.entries.last_mut().unwrap()
+            }
+        };
+
+        if !entry.lines.contains(&line) {
+            entry.lines.push(line);
+        }
+    }
+
+    pub fn render(&self, format: GlossaryFormat) -> String {
+        match format {
+            GlossaryFormat::Markdown => self.as_markdown(),
+            GlossaryFormat::Latex => self.as_latex(),
+            GlossaryFormat::Html => self.as_html(),
+        }
+    }
+
+    pub fn as_markdown(&self) -> String {
+        let escape = |text: &str| text.replace('|', "\\|");
+        let mut lines: Vec<String> = vec![
+            "| Word | Grammar | Meaning | Lines |".to_string(),
+            "| --- | --- | --- | --- |".to_string(),
+        ];
+
+        for entry in &self.entries {
+            lines.push(format!(
+                "| **{}** | {} | {} | {} |",
+                escape(&entry.dictionary_form),
+                escape(&entry.grammar),
+                escape(&entry.sense),
+                join_lines(&entry.lines)
+            ));
+        }
+
+        lines.join("\n")
+    }
+
+    pub fn as_latex(&self) -> String {
+        let mut lines: Vec<String> = vec!["\\begin{description}".to_string()];
+
+        for entry in &self.entries {
+            lines.push(format!(
+                "  \\item[{}] \\textit{{{}}} {} ({})",
+                escape_latex(&entry.dictionary_form),
+                escape_latex(&entry.grammar),
+                escape_latex(&entry.sense),
+                join_lines(&entry.lines)
+            ));
+        }
+
+        lines.push("\\end{description}".to_string());
+        lines.join("\n")
+    }
+
+    pub fn as_html(&self) -> String {
+        let mut lines: Vec<String> = vec!["<dl class=\"glossary\">".to_string()];
+
+        for entry in &self.entries {
+            lines.push(format!(
+                "  <dt>{}</dt>",
+                escape_html(&entry.dictionary_form)
+            ));
+            lines.push(format!(
+                "  <dd><i>{}</i> {} <span class=\"lines\">{}</span></dd>",
+                escape_html(&entry.grammar),
+                escape_html(&entry.sense),
+                join_lines(&entry.lines)
+            ));
+        }
+
+        lines.push("</dl>".to_string());
+        lines.join("\n")
+    }
+}
+
+/**
+ * Whether a word is in a list of known words (in lower case): its headword is, or the word itself is.
+ */
+pub fn is_known(known_words: &HashSet<String>, headword: &str, word: &str) -> bool {
+    known_words.contains(&headword.to_lowercase()) || known_words.contains(&word.to_lowercase())
+}
+
+// ex: f., 1st declension | 3rd conjugation | adverb
+fn describe_grammar(definition: &LatinTranslationInfo) -> String {
+    let pos = definition.word.pos;
+    let form = match &definition.word.form {
+        Form::LongForm(form) => form,
+        Form::StrForm(_) => return pos.as_str().to_string(),
+    };
+
+    let mut grammar: Vec<String> = Vec::new();
+    if pos == PartOfSpeech::Noun {
+        if let Some(gender) = form.gender.and_then(abbreviate_gender) {
+            grammar.push(gender.to_string());
+        }
+    }
+
+    let kind = match pos {
+        PartOfSpeech::Noun | PartOfSpeech::Adjective => form.declension_type.as_ref(),
+        PartOfSpeech::Verb => form.verb_type.as_ref(),
+        _ => None,
+    };
+    match kind.filter(|kind| *kind != "unknown") {
+        Some(kind) if pos == PartOfSpeech::Adjective => grammar.push(format!("adj., {kind}")),
+        Some(kind) => grammar.push(kind.clone()),
+        None => grammar.push(pos.as_str().to_string()),
+    }
+
+    grammar.join(", ")
+}
+
+fn abbreviate_gender(gender: Gender) -> Option<&'static str> {
+    match gender {
+        Gender::Masculine => Some("m."),
+        Gender::Feminine => Some("f."),
+        Gender::Neuter => Some("n."),
+        Gender::Common => Some("m./f."),
+        Gender::Unknown => None,
+    }
+}
+
+// the first meaning of the first sense, senses often list several (girl, maiden;)
+fn short_sense(senses: &[String]) -> String {
+    let sense = senses.first().map_or("", |sense| sense.as_str());
+    sense
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+fn join_lines(lines: &[usize]) -> String {
+    let lines: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    lines.join(", ")
+}
+
+fn escape_latex(text: &str) -> String {
+    let mut escaped = String::new();
+
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,108 @@
+use crate::dictionary_structures::dictionary_keys::{Age, Frequency, PartOfSpeech};
+use crate::translators::latin_to_english::disambiguation::Reading;
+use crate::utils::tokenizer::{SourceSpan, Token};
+use serde::{Deserialize, Serialize};
+
+pub const TSV_HEADER: &str = "token\tline\tcolumn\tid\theadword\tpos\talternatives";
+
+/**
+ * A dictionary entry a word can be a form of, `headword` is its first principal part.
+ * `id` is 0 for entries that are not in the dictionary (names, compounds).
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Lemma {
+    pub id: i32,
+    pub headword: String,
+    pub pos: PartOfSpeech,
+    // how frequent the dictionary says the word is
+    pub frequency: Frequency,
+    // when the dictionary says the word was used
+    pub age: Age,
+    pub score: i32,
+}
+
+/**
+ * A word of a text and its lemma, the best one by the words around it, and the other lemmas it can have, the best first.
+ * Words that are not found have no lemma.
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LemmatizedToken {
+    pub token: String,
+    pub span: SourceSpan,
+    pub lemma: Option<Lemma>,
+    pub alternatives: Vec<Lemma>,
+}
+
+impl LemmatizedToken {
+    /**
+     * Makes the lemmas from the ranked readings of the word, one for each dictionary entry.
+     * Readings guessed from the ending of a word are not lemmas.
+     */
+    pub fn new(token: &Token, readings: &[Reading]) -> LemmatizedToken {
+        let mut lemmas: Vec<Lemma> = Vec::new();
+
+        for reading in readings
+            .iter()
+            .filter(|reading| reading.definition.guessed_from.is_none())
+        {
+            let mut word = reading.definition.word.clone();
+            word.generate_principle_parts();
+            let headword = word.parts.first().unwrap_or(&word.orth).clone();
+
+            // readings are ranked, so the first one of each entry has its best score
+            if !lemmas
+                .iter()
+                .any(|lemma| lemma.id == word.id && lemma.headword == headword)
+            {
+                lemmas.push(Lemma {
+                    id: word.id,
+                    headword,
+                    pos: reading.definition.get_pos(),
+                    frequency: word.info.freq,
+                    age: word.info.age,
+                    score: reading.score,
+                });
+            }
+        }
+
+        let mut lemmas = lemmas.into_iter();
+        LemmatizedToken {
+            token: token.text.clone(),
+            span: token.span,
+            lemma: lemmas.next(),
+            alternatives: lemmas.collect(),
+        }
+    }
+
+    /**
+     * One line of tab separated values, in the order of `TSV_HEADER`.
+     * The alternatives are written as headword:id, separated by |.
+     */
+    pub fn as_tsv(&self) -> String {
+        let (id, headword, pos) = match &self.lemma {
+            Some(lemma) => (
+                lemma.id.to_string(),
+                lemma.headword.clone(),
+                lemma.pos.as_str().to_string(),
+            ),
+            None => (String::new(), String::new(), String::new()),
+        };
+        let alternatives: Vec<String> = self
+            .alternatives
+            .iter()
+            .map(|lemma| format!("{}:{}", lemma.headword, lemma.id))
+            .collect();
+
+        format!(
+            "{}\t{}\t{}\t{id}\t{headword}\t{pos}\t{}",
+            self.token,
+            self.span.line,
+            self.span.column,
+            alternatives.join("|")
+        )
+    }
+
+    pub fn as_json_line(&self) -> String {
+        serde_json::to_string(&self).unwrap()
+    }
+}
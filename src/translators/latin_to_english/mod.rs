@@ -1,27 +1,56 @@
+pub mod checker;
+pub mod compound;
+pub mod difficulty;
+pub mod disambiguation;
+pub mod frequency;
+pub mod glossary;
+pub mod lemmatizer;
+pub mod names;
 pub mod parser;
+pub mod phrases;
+pub mod suggestions;
 pub mod translator;
 pub mod tricks;
 pub mod utils;
 
-use self::tricks::{try_syncopes, try_tricks};
+use self::tricks::{
+    try_medieval_spellings, try_syncopes, try_tricks, TrickKind, TrickResult, TrickStep,
+};
 use crate::dictionary_structures::dictionary_keys::{
     Age, Area, Frequency, Geography, PartOfSpeech, Source,
 };
 use crate::dictionary_structures::dictionary_values::{
-    Form, Inflection, LatinWordInfo, LongForm, Stem,
+    Form, Inflection, LatinWordInfo, LongForm, Modifier, ModifierType, NValue, Stem,
 };
 use crate::utils::{evaluate_roman_numeral, is_roman_number};
 use serde::{Deserialize, Serialize};
 
-use self::parser::parse;
-use self::utils::split_enclitic;
+use self::compound::find_compounds;
+use self::parser::{guess_from_ending, parse};
+use self::utils::{attachment_fits, split_enclitics};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct LatinTranslationInfo {
-    pub tricks: Option<Vec<String>>,
+    pub tricks: Option<Vec<TrickStep>>,
     pub word: LatinWordInfo,
     pub stem: Stem,
     pub inflections: Option<Vec<Inflection>>,
+    pub derived: Option<Derivation>,
+    // the two words a compound was found as, ex: reipublicae -> res + publicus
+    pub compound: Option<Vec<LatinTranslationInfo>>,
+    // the ending a word missing from the dictionary was guessed from
+    pub guessed_from: Option<String>,
+}
+
+/**
+ * What a word made with a suffix is, when it is not the same as the word it was made from.
+ * ex: aquaticus is found as aqua, but is an adjective of the 1st and 2nd declension.
+ */
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct Derivation {
+    pub pos: PartOfSpeech,
+    pub n: Option<Vec<NValue>>,
+    pub form: String,
 }
 
 impl Default for LatinTranslationInfo {
@@ -37,14 +66,18 @@ impl LatinTranslationInfo {
             word: LatinWordInfo::new(),
             stem: Stem::new(),
             inflections: None,
+            derived: None,
+            compound: None,
+            guessed_from: None,
         }
     }
 
     pub fn remove_inflections_with_wrong_pos(&mut self) {
         if self.inflections.is_some() {
+            let pos = self.get_pos();
             let mut new_inflections = Vec::new();
             for inflection in self.inflections.as_ref().unwrap() {
-                if inflection.pos == self.word.pos {
+                if inflection.pos == pos {
                     new_inflections.push(inflection.clone());
                 }
             }
@@ -52,6 +85,16 @@ impl LatinTranslationInfo {
         }
     }
 
+    /**
+     * The part of speech of the word that was searched, which is not the one of the dictionary entry for derived words
+     */
+    pub fn get_pos(&self) -> PartOfSpeech {
+        match &self.derived {
+            Some(derived) => derived.pos,
+            None => self.word.pos,
+        }
+    }
+
     pub fn set_word(&mut self, word: &LatinWordInfo) {
         self.word = word.clone();
     }
@@ -64,12 +107,52 @@ impl LatinTranslationInfo {
         self.inflections = Some(inflections);
     }
 
-    pub fn set_tricks(&mut self, tricks: &[String]) {
+    pub fn set_tricks(&mut self, tricks: &[TrickStep]) {
         self.tricks = Some(tricks.to_vec());
     }
+
+    pub fn get_trick_cost(&self) -> usize {
+        match &self.tricks {
+            Some(tricks) => tricks.iter().map(|trick| trick.cost).sum(),
+            None => 0,
+        }
+    }
+
+    /**
+     * The tricks used to find the word, as prose
+     */
+    pub fn get_trick_explanations(&self) -> Vec<String> {
+        match &self.tricks {
+            Some(tricks) => tricks.iter().map(|trick| trick.to_string()).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+pub fn translate_latin_to_english(
+    latin_word: &str,
+    tricks: bool,
+    medieval: bool,
+) -> Vec<LatinTranslationInfo> {
+    let output = find_latin_words(latin_word, tricks, medieval);
+
+    // the word is not in the dictionary, so all that can be given is what its ending could be
+    if output.is_empty() {
+        return guess_from_ending(latin_word).unwrap_or_default();
+    }
+
+    output
 }
 
-pub fn translate_latin_to_english(latin_word: &str, tricks: bool) -> Vec<LatinTranslationInfo> {
+/**
+ * Looks the word up like `translate_latin_to_english`, without guessing what a word missing from the dictionary could be.
+ * Used when only the words of the dictionary are wanted, as guessing is slow.
+ */
+pub fn find_latin_words(
+    latin_word: &str,
+    tricks: bool,
+    medieval: bool,
+) -> Vec<LatinTranslationInfo> {
     if is_roman_number(latin_word) {
         match evaluate_roman_numeral(latin_word) {
             Ok(number) => {
@@ -101,54 +184,174 @@ pub fn translate_latin_to_english(latin_word: &str, tricks: bool) -> Vec<LatinTr
     let mut output = parse(latin_word, false);
 
     if tricks {
-        let trick_results = try_tricks(latin_word);
+        // only the cheapest tricks that find a word are kept
+        let mut found_cost: Option<usize> = None;
+        let mut candidates = vec![TrickResult::Found(latin_word.to_string(), Vec::new())];
+        candidates.extend(try_tricks(latin_word));
 
-        let mut modified_word = if trick_results.is_found() {
-            trick_results.get_word()
-        } else {
-            latin_word.to_string()
-        };
-        let mut explanations = trick_results.get_explanations();
+        for candidate in candidates {
+            if found_cost.is_some_and(|cost| candidate.get_cost() > cost) {
+                break;
+            }
 
-        let syncope_results = try_syncopes(&modified_word);
+            let candidate_word = candidate.get_word();
+            let steps = candidate.get_steps();
 
-        if syncope_results.get_word() != modified_word && syncope_results.is_found() {
-            modified_word = syncope_results.get_word();
-            explanations.extend(syncope_results.get_explanations());
-        }
+            if !steps.is_empty() {
+                if let Some(new_output) = parse(&candidate_word, false) {
+                    add_new_words(&mut output, new_output, &steps);
+                    found_cost.get_or_insert(candidate.get_cost());
+                }
+            }
 
-        if modified_word != latin_word && modified_word != String::new() {
-            let mut new_output = parse(&modified_word, false);
+            // contracted forms have to be expanded before their stems can be found
+            for syncope in try_syncopes(&candidate_word) {
+                if let Some(mut new_output) = parse(&syncope.get_word(), false) {
+                    new_output.retain(|word| {
+                        word.word.pos == PartOfSpeech::Verb
+                            || word.word.pos == PartOfSpeech::Participle
+                    });
 
-            if let Some(new_output) = &mut new_output {
-                for word in new_output.iter_mut() {
-                    word.set_tricks(&explanations);
-                }
+                    if new_output.is_empty() {
+                        continue;
+                    }
 
-                if let Some(output) = &mut output {
-                    output.extend_from_slice(new_output);
-                } else {
-                    output = Some(new_output.clone());
+                    let mut syncope_steps = steps.clone();
+                    syncope_steps.extend(syncope.get_steps());
+                    add_new_words(&mut output, new_output, &syncope_steps);
+                    found_cost.get_or_insert(candidate.get_cost() + syncope.get_cost());
                 }
             }
         }
     }
 
+    // medieval spellings are only tried when asked for, most of them would give wrong results for classical texts
+    if medieval {
+        for variant in try_medieval_spellings(latin_word) {
+            if let Some(new_output) = parse(&variant.get_word(), true) {
+                add_new_words(&mut output, new_output, &variant.get_steps());
+            }
+        }
+    }
+
     // most words should be found by now
 
-    // If nothing is found, try removing enclitics and try again
-    // ex: clamaverunt -> clamare
-    // doing this here instead of earlier should fix words like salve having the "ve" removed and returning wrong def
+    // words can also be read with addons removed, even if the whole word was found
+    // ex: clamaverunt -> clamare, salve -> salve and sal + ve
+    // these readings come after the ones of the whole word
+    for (split_word, modifiers) in split_enclitics(latin_word) {
+        let new_output = match parse(&split_word, false) {
+            Some(new_output) => new_output,
+            None => continue,
+        };
+
+        let new_output: Vec<LatinTranslationInfo> = new_output
+            .into_iter()
+            .filter(|word| {
+                modifiers
+                    .iter()
+                    .all(|modifier| attachment_fits(modifier, word.get_pos()))
+            })
+            .map(|mut word| {
+                let mut word_modifiers = modifiers.clone();
+                word_modifiers.extend(word.word.modifiers.take().unwrap_or_default());
+                word.word.set_modifiers(word_modifiers);
+                word
+            })
+            .collect();
+
+        let steps = enclitic_steps(latin_word, &modifiers);
+        add_new_words(&mut output, new_output, &steps);
+    }
+
+    // compounds where both parts inflect are only tried when nothing else was found
+    // ex: reipublicae -> res publica
     if output.is_none() {
-        let (word_without_ecliptics, modifiers) = split_enclitic(latin_word);
-        output = parse(&word_without_ecliptics, false);
+        output = find_compounds(latin_word);
+    }
+
+    output.unwrap_or_default()
+}
+
+/**
+ * Adds words found with a trick to the output, unless the word was already found.
+ */
+fn add_new_words(
+    output: &mut Option<Vec<LatinTranslationInfo>>,
+    new_output: Vec<LatinTranslationInfo>,
+    steps: &[TrickStep],
+) {
+    for mut word in new_output {
+        let already_found = output.as_ref().is_some_and(|output| {
+            output
+                .iter()
+                .any(|x| x.word.id == word.word.id && x.word.orth == word.word.orth)
+        });
+
+        if already_found {
+            continue;
+        }
 
-        if let Some(output) = &mut output {
-            for word in output.iter_mut() {
-                word.word.set_modifiers(modifiers.clone());
+        if !steps.is_empty() {
+            match &mut word.tricks {
+                // keeps the steps taken to find the stem, after the ones taken on the whole word
+                Some(stem_steps) => {
+                    let mut all_steps = steps.to_vec();
+                    all_steps.append(stem_steps);
+                    word.set_tricks(&all_steps);
+                }
+                None => word.set_tricks(steps),
             }
         }
+
+        output.get_or_insert_with(Vec::new).push(word);
     }
+}
 
-    output.unwrap_or_default()
+/**
+ * Gives a step for each addon removed from the word (tickons from its start, enclitics from its end), in the order they were removed.
+ */
+fn enclitic_steps(latin_word: &str, modifiers: &[Modifier]) -> Vec<TrickStep> {
+    let mut steps: Vec<TrickStep> = Vec::new();
+    let mut word = latin_word.to_string();
+
+    for modifier in modifiers {
+        // tickons are at the start of the word, the other addons at its end
+        if modifier.modifier == ModifierType::Tickon {
+            if !word.starts_with(&modifier.orth) {
+                continue;
+            }
+
+            word = word[modifier.orth.len()..].to_string();
+            steps.push(TrickStep::new(
+                TrickKind::Prefix,
+                &modifier.orth,
+                "",
+                Some(0),
+                &word,
+                &format!("The tickon '{}' may be attached to '{word}'", modifier.orth),
+            ));
+            continue;
+        }
+
+        if !word.ends_with(&modifier.orth) {
+            continue;
+        }
+
+        let position = word.len() - modifier.orth.len();
+        word.truncate(position);
+        steps.push(TrickStep::new(
+            TrickKind::Enclitic,
+            &modifier.orth,
+            "",
+            Some(position),
+            &word,
+            &format!(
+                "The enclitic '{}' may be attached to '{word}'",
+                modifier.orth
+            ),
+        ));
+    }
+
+    steps
 }
@@ -0,0 +1,145 @@
+use crate::dictionary_structures::dictionary_keys::{Noun, PartOfSpeech};
+use crate::dictionary_structures::dictionary_values::{
+    Form, Inflection, LatinName, LongForm, NValue, NameKind,
+};
+use crate::translators::latin_to_english::parser::{describe_guess, guess_from_ending};
+use crate::translators::latin_to_english::LatinTranslationInfo;
+use crate::utils::data::{get_latin_inflections, get_latin_names};
+
+/**
+ * Finds the word in the names lists the user gave, as a name stem followed by a noun ending it can take.
+ * ex: Marci -> Marcus (praenomen), genitive singular
+ */
+pub fn find_names(latin_word: &str) -> Option<Vec<LatinTranslationInfo>> {
+    let latin_word = latin_word.to_lowercase();
+    let mut output: Vec<LatinTranslationInfo> = Vec::new();
+
+    for name in get_latin_names() {
+        let inflections: Vec<Inflection> = match latin_word.strip_prefix(&name.stem.to_lowercase())
+        {
+            Some(ending) => get_latin_inflections()
+                .iter()
+                .filter(|inflection| {
+                    inflection.ending == ending && name_fits_inflection(name, inflection)
+                })
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+
+        // the name as it is written in the list is always found, even when it is not its stem and an ending (Cicero, Ciceron-is)
+        if inflections.is_empty() && latin_word != name.orth.to_lowercase() {
+            continue;
+        }
+
+        let mut translation = LatinTranslationInfo::new();
+        translation.word.set_orth(&name.orth);
+        translation.word.set_pos(PartOfSpeech::Noun);
+        translation.word.set_form(Form::StrForm(format!(
+            "{} {} {} {}",
+            name.n.first().unwrap_or(&0),
+            name.n.get(1).unwrap_or(&0),
+            name.gender.as_deref().unwrap_or("X"),
+            match name.kind {
+                NameKind::Place => "L",
+                _ => "N",
+            }
+        )));
+        translation.word.set_senses(if name.senses.is_empty() {
+            vec![name.kind.as_str().to_string()]
+        } else {
+            name.senses.clone()
+        });
+
+        translation.stem.orth = name.stem.clone();
+        translation.stem.pos = PartOfSpeech::Noun;
+        translation.stem.n = Some(name.n.iter().map(|n| NValue::Integer(*n)).collect());
+        translation.set_inflections(inflections);
+
+        output.push(translation);
+    }
+
+    if output.is_empty() {
+        None
+    } else {
+        Some(output)
+    }
+}
+
+// the ending has to be one of a noun of the name's declension, and of its gender if it has one
+fn name_fits_inflection(name: &LatinName, inflection: &Inflection) -> bool {
+    if inflection.pos != PartOfSpeech::Noun {
+        return false;
+    }
+
+    let n_fits = |index: usize| {
+        let inflection_n = inflection
+            .n
+            .as_ref()
+            .and_then(|n| n.get(index))
+            .map_or(0, |n| n.get_n_value_1());
+        inflection_n == 0 || name.n.get(index).is_none_or(|n| *n == inflection_n)
+    };
+    if !n_fits(0) || !n_fits(1) {
+        return false;
+    }
+
+    let gender = match &name.gender {
+        Some(gender) => gender.as_str(),
+        None => return true,
+    };
+    let inflection_gender = match &inflection.form {
+        Form::StrForm(form) => form.split_whitespace().nth(2).unwrap_or("X"),
+        Form::LongForm(_) => "X",
+    };
+
+    match inflection_gender {
+        "X" => true,
+        "C" => gender == "M" || gender == "F",
+        inflection_gender => inflection_gender == gender,
+    }
+}
+
+/**
+ * Reads a capitalized word that is not in the dictionary as a name, guessing its declension from its ending.
+ * ex: Vercingetorigis -> Vercingetorig-is, a 3rd declension noun in the genitive singular
+ */
+pub fn guess_proper_name(latin_word: &str) -> Option<Vec<LatinTranslationInfo>> {
+    let mut guesses = guess_from_ending(latin_word)?;
+    guesses.retain(|guess| guess.word.pos == PartOfSpeech::Noun);
+
+    for guess in guesses.iter_mut() {
+        let n = guess
+            .stem
+            .n
+            .as_ref()
+            .and_then(|n| n.first())
+            .map_or(0, |n| n.get_n_value_1());
+        let ending = guess.guessed_from.clone().unwrap_or_default();
+
+        guess.word.set_orth(&capitalize(&guess.word.orth));
+        guess.stem.orth = capitalize(&guess.stem.orth);
+        guess.word.set_senses(vec![format!(
+            "Probable proper name, {} guessed from the ending -{ending}",
+            describe_guess(PartOfSpeech::Noun, n)
+        )]);
+
+        let mut form = LongForm::new().set_part_of_speech(PartOfSpeech::Noun);
+        form.noun = Some(Noun::ProperName);
+        guess.word.set_form(Form::LongForm(form));
+    }
+
+    if guesses.is_empty() {
+        None
+    } else {
+        Some(guesses)
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
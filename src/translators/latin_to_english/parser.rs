@@ -1,10 +1,12 @@
 use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
-use crate::dictionary_structures::dictionary_values::{Inflection, LatinWordInfo, NValue, Stem};
+use crate::dictionary_structures::dictionary_values::{
+    Form, Inflection, LatinWordInfo, LongForm, NValue, Stem,
+};
 use crate::translators::latin_to_english::translator::lookup_stems;
-use crate::translators::latin_to_english::tricks::{try_medieval_tricks, TrickResult};
-use crate::translators::latin_to_english::utils::reduce;
-use crate::translators::latin_to_english::LatinTranslationInfo;
+use crate::translators::latin_to_english::utils::{reduce, SuffixForm};
+use crate::translators::latin_to_english::{Derivation, LatinTranslationInfo};
 use crate::utils::data::{get_latin_inflections, get_latin_stems, get_unique_latin_words};
+use crate::utils::number_with_ending;
 
 pub fn parse(latin_word: &str, reduced: bool) -> Option<Vec<LatinTranslationInfo>> {
     match find_form(latin_word, reduced) {
@@ -46,26 +48,134 @@ pub fn find_form(latin_word: &str, reduced: bool) -> Option<Vec<LatinTranslation
         }
     }
 
-    let (stems, inflections) = check_stems(latin_word, &latin_word_inflections, false);
+    let (stems, inflections) = check_stems(latin_word, &latin_word_inflections);
     let mut output = lookup_stems(stems, inflections);
 
     if output.is_none() && !reduced {
         output = reduce(latin_word);
     }
 
-    //curebantur -> currebantur (needs to work on stem or word: cureb -> curreb)
-    if output.is_none() {
-        let (stems, inflections) = check_stems(latin_word, &latin_word_inflections, true);
-        output = lookup_stems(stems, inflections);
+    output
+}
+
+// the shortest stem an unknown word is guessed to have
+const MIN_GUESSED_STEM_LENGTH: usize = 2;
+// words missing from the dictionary are almost always one of these
+const GUESSED_PARTS_OF_SPEECH: [PartOfSpeech; 4] = [
+    PartOfSpeech::Noun,
+    PartOfSpeech::Adjective,
+    PartOfSpeech::Verb,
+    PartOfSpeech::Participle,
+];
+
+/**
+ * Guesses what a word missing from the dictionary could be from its ending alone.
+ * Only the longest ending that fits each part of speech is used, with the rest of the word as the stem.
+ * Gives one word for each part of speech, declension or conjugation and ending.
+ * ex: -ibus -> 3rd declension noun or adjective, dative or ablative plural
+ */
+pub fn guess_from_ending(latin_word: &str) -> Option<Vec<LatinTranslationInfo>> {
+    let inflections: Vec<&Inflection> = get_latin_inflections()
+        .iter()
+        .filter(|inflection| {
+            // a word with no ending could be anything, and closed classes (pronouns, numerals...) are all in the dictionary
+            !inflection.ending.is_empty()
+                && latin_word.ends_with(&inflection.ending)
+                && latin_word[..latin_word.len() - inflection.ending.len()]
+                    .chars()
+                    .count()
+                    >= MIN_GUESSED_STEM_LENGTH
+                && GUESSED_PARTS_OF_SPEECH.contains(&inflection.pos)
+        })
+        .collect();
+
+    let mut output: Vec<LatinTranslationInfo> = Vec::new();
+
+    for inflection in &inflections {
+        let longest_ending = inflections
+            .iter()
+            .filter(|other| other.pos == inflection.pos)
+            .map(|other| other.ending.len())
+            .max()
+            .unwrap_or(0);
+
+        if inflection.ending.len() < longest_ending {
+            continue;
+        }
+
+        let n = first_n_value(&inflection.n);
+        let guess = output.iter_mut().find(|word| {
+            word.word.pos == inflection.pos
+                && first_n_value(&word.stem.n) == n
+                && word.guessed_from.as_ref() == Some(&inflection.ending)
+        });
+
+        match guess {
+            Some(guess) => guess
+                .inflections
+                .get_or_insert_with(Vec::new)
+                .push((*inflection).clone()),
+            None => {
+                let stem_orth = &latin_word[..latin_word.len() - inflection.ending.len()];
+
+                let mut guess = LatinTranslationInfo::new();
+                guess.word.set_orth(stem_orth);
+                guess.word.set_pos(inflection.pos);
+                guess.word.set_form(Form::LongForm(
+                    LongForm::new().set_part_of_speech(inflection.pos),
+                ));
+                guess.word.set_senses(vec![format!(
+                    "Unknown {}, guessed from the ending -{}",
+                    describe_guess(inflection.pos, n),
+                    inflection.ending
+                )]);
+
+                guess.stem.orth = stem_orth.to_string();
+                guess.stem.pos = inflection.pos;
+                guess.stem.n = inflection.n.clone();
+
+                guess.guessed_from = Some(inflection.ending.clone());
+                guess.set_inflections(vec![(*inflection).clone()]);
+                output.push(guess);
+            }
+        }
     }
 
-    output
+    if output.is_empty() {
+        return None;
+    }
+
+    // longer endings say more about the word
+    output.sort_by_key(|guess| {
+        std::cmp::Reverse(guess.guessed_from.as_ref().map_or(0, |ending| ending.len()))
+    });
+    Some(output)
+}
+
+fn first_n_value(n: &Option<Vec<NValue>>) -> i8 {
+    n.as_ref()
+        .and_then(|n| n.first())
+        .map_or(0, |n| n.get_n_value_1())
+}
+
+// ex: 3rd declension noun, 1st conjugation verb
+pub fn describe_guess(pos: PartOfSpeech, n: i8) -> String {
+    if n <= 0 {
+        return pos.as_str().to_string();
+    }
+
+    match pos {
+        PartOfSpeech::Verb => format!("{} conjugation verb", number_with_ending(n)),
+        PartOfSpeech::Noun | PartOfSpeech::Adjective => {
+            format!("{} declension {}", number_with_ending(n), pos.as_str())
+        }
+        _ => pos.as_str().to_string(),
+    }
 }
 
 fn check_stems(
     latin_word: &str,
     latin_word_inflections: &Vec<Inflection>,
-    tricks: bool,
 ) -> (Vec<Stem>, Vec<Inflection>) {
     let latin_stems = get_latin_stems();
     let mut matched_stems: Vec<Stem> = Vec::new();
@@ -76,19 +186,6 @@ fn check_stems(
         //TODO: if the inflection.ending is "" and word cant take inflection endings, continue
         let word_stem = latin_word.trim_end_matches(&inflection.ending);
 
-        //TODO: add trick explanation
-        let word_stem = match tricks {
-            true => {
-                let tricked = try_medieval_tricks(word_stem);
-
-                match tricked {
-                    TrickResult::Found(word, _) => word,
-                    TrickResult::NotFound => word_stem.to_string(),
-                }
-            }
-            false => word_stem.to_string(),
-        };
-
         for stem in latin_stems {
             if word_stem == stem.orth
                 && (inflection.pos == stem.pos
@@ -155,3 +252,74 @@ fn check_stems(
 
     (matched_stems, inflections)
 }
+
+/**
+ * The ways the word can end with the suffix and then an ending that fits it, as the part before the suffix and the ending.
+ * ex: aquaticus with atic -> (aqu, us)
+ */
+pub fn suffixed_bases<'a>(
+    latin_word: &'a str,
+    suffix_orth: &str,
+    suffix_form: &SuffixForm,
+) -> Vec<(&'a str, &'static Inflection)> {
+    let mut bases: Vec<(&'a str, &'static Inflection)> = Vec::new();
+
+    for inflection in get_latin_inflections() {
+        if !latin_word.ends_with(&inflection.ending) || !suffix_form.fits_inflection(inflection) {
+            continue;
+        }
+
+        match latin_word[..latin_word.len() - inflection.ending.len()].strip_suffix(suffix_orth) {
+            Some(base) if !base.is_empty() => bases.push((base, inflection)),
+            _ => continue,
+        }
+    }
+
+    bases
+}
+
+/**
+ * Finds words made of a stem, the suffix and an ending (ex: aqu + atic + us).
+ * The ending has to fit the part of speech and declension the suffix gives the word,
+ * and the stem has to be of the part of speech the suffix attaches to.
+ */
+pub fn find_suffixed_form(
+    latin_word: &str,
+    suffix_orth: &str,
+    suffix_form: &SuffixForm,
+) -> Option<Vec<LatinTranslationInfo>> {
+    let mut matched_stems: Vec<Stem> = Vec::new();
+    let mut inflections: Vec<Inflection> = Vec::new();
+
+    for (base, inflection) in suffixed_bases(latin_word, suffix_orth, suffix_form) {
+        for stem in get_latin_stems() {
+            if stem.orth == base && suffix_form.fits_stem(stem) {
+                if !matched_stems
+                    .iter()
+                    .any(|x| x.wid == stem.wid && x.orth == stem.orth)
+                {
+                    matched_stems.push(stem.clone());
+                }
+                if !inflections
+                    .iter()
+                    .any(|x| x.ending == inflection.ending && x.form == inflection.form)
+                {
+                    inflections.push(inflection.clone());
+                }
+            }
+        }
+    }
+
+    let mut output = lookup_stems(matched_stems, inflections)?;
+
+    let derivation = Derivation {
+        pos: suffix_form.target_pos,
+        n: suffix_form.target_n.clone(),
+        form: suffix_form.target_form.clone(),
+    };
+    for word in output.iter_mut() {
+        word.derived = Some(derivation.clone());
+    }
+
+    Some(output)
+}
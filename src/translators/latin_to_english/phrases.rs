@@ -0,0 +1,113 @@
+use crate::dictionary_structures::dictionary_values::{LatinPhrase, PhraseWord};
+use crate::translators::latin_to_english::LatinTranslationInfo;
+use crate::utils::data::get_latin_phrases;
+use serde::{Deserialize, Serialize};
+
+/**
+ * A phrase found in the text, `words` are the indexes of its words among the words of the text.
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PhraseMatch {
+    pub phrase: String,
+    pub senses: Vec<String>,
+    pub words: Vec<usize>,
+}
+
+/**
+ * A word of the text as phrases see it: how it was looked up, the dictionary entries it can be a form of,
+ * and the sentence it is in, as a phrase can not go over the end of a sentence.
+ */
+#[derive(Debug, Clone)]
+pub struct PhraseCandidate {
+    pub form: String,
+    pub lemmas: Vec<String>,
+    pub sentence: usize,
+}
+
+impl PhraseCandidate {
+    pub fn new(
+        form: &str,
+        definitions: &[LatinTranslationInfo],
+        sentence: usize,
+    ) -> PhraseCandidate {
+        let mut lemmas: Vec<String> = Vec::new();
+
+        // words that were only guessed are not forms of any dictionary entry
+        for definition in definitions
+            .iter()
+            .filter(|definition| definition.guessed_from.is_none())
+        {
+            let orths = std::iter::once(&definition.word.orth).chain(definition.word.parts.first());
+            for orth in orths {
+                let lemma = orth.to_lowercase();
+                if !lemma.is_empty() && !lemmas.contains(&lemma) {
+                    lemmas.push(lemma);
+                }
+            }
+        }
+
+        PhraseCandidate {
+            form: form.to_lowercase(),
+            lemmas,
+            sentence,
+        }
+    }
+}
+
+/**
+ * Finds every phrase of the phrase lexicon in the words of a text, phrases can overlap.
+ * The words of a phrase have to be in order, with at most `max_gap` other words between two of them.
+ */
+pub fn find_phrases(words: &[PhraseCandidate]) -> Vec<PhraseMatch> {
+    let mut matches: Vec<PhraseMatch> = Vec::new();
+
+    for start in 0..words.len() {
+        for phrase in get_latin_phrases() {
+            if let Some(indexes) = match_phrase(phrase, words, start) {
+                matches.push(PhraseMatch {
+                    phrase: phrase.phrase.clone(),
+                    senses: phrase.senses.clone(),
+                    words: indexes,
+                });
+            }
+        }
+    }
+
+    matches
+}
+
+// the closest word that fits is taken for each word of the phrase
+fn match_phrase(
+    phrase: &LatinPhrase,
+    words: &[PhraseCandidate],
+    start: usize,
+) -> Option<Vec<usize>> {
+    let (first_word, other_words) = phrase.words.split_first()?;
+    if !word_fits(first_word, &words[start]) {
+        return None;
+    }
+
+    let sentence = words[start].sentence;
+    let mut indexes = vec![start];
+
+    for phrase_word in other_words {
+        let last = *indexes.last()?;
+        let next = (last + 1..=last + 1 + phrase.max_gap)
+            .take_while(|&i| i < words.len() && words[i].sentence == sentence)
+            .find(|&i| word_fits(phrase_word, &words[i]))?;
+        indexes.push(next);
+    }
+
+    Some(indexes)
+}
+
+fn word_fits(phrase_word: &PhraseWord, word: &PhraseCandidate) -> bool {
+    phrase_word
+        .form
+        .as_ref()
+        .is_none_or(|form| *form == word.form)
+        && phrase_word
+            .lemma
+            .as_ref()
+            .is_none_or(|lemma| word.lemmas.contains(lemma))
+}
@@ -0,0 +1,324 @@
+use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
+use crate::dictionary_structures::dictionary_values::{Inflection, NValue, Stem};
+use crate::translators::latin_to_english::parser::parse;
+use crate::translators::latin_to_english::LatinTranslationInfo;
+use crate::utils::data::{get_latin_inflections, get_latin_stems, get_unique_latin_words};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+// the cost of adding, removing or changing a letter
+const EDIT_COST: usize = 2;
+// the cost of a change that is a common scribal or OCR mistake
+const CONFUSION_COST: usize = 1;
+// the furthest a suggestion can be from the word, two changed letters or four common mistakes
+const MAX_DISTANCE: usize = 2 * EDIT_COST;
+// the most letters a stem can differ by from the start of the word
+const MAX_STEM_DISTANCE: usize = 2;
+// the longest ending taken off the word before looking for its stem
+const MAX_ENDING_LENGTH: usize = 6;
+
+/**
+ * Letters that are often mistaken for each other, by scribes or by OCR.
+ * ex: u is read as n, rn as m, cl as d
+ */
+pub const CONFUSIONS: [(&str, &str); 8] = [
+    ("u", "n"),
+    ("c", "e"),
+    ("rn", "m"),
+    ("u", "v"),
+    ("i", "j"),
+    ("i", "l"),
+    ("cl", "d"),
+    ("li", "h"),
+];
+
+/**
+ * A word that is in the dictionary and close to a word that was not found, with what it was parsed as.
+ */
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Suggestion {
+    pub word: String,
+    pub distance: usize,
+    pub definitions: Vec<LatinTranslationInfo>,
+}
+
+/**
+ * Gives up to `count` words that are in the dictionary and close to the word, closest first.
+ * A suggestion is a known stem with an ending it can take, or a unique word,
+ * so the word is looked at as a stem that may be misspelled followed by an ending that may be misspelled.
+ */
+pub fn suggest(latin_word: &str, count: usize) -> Vec<Suggestion> {
+    if count == 0 {
+        return Vec::new();
+    }
+
+    let index = get_stem_index();
+    let word_chars: Vec<char> = latin_word.chars().collect();
+    let mut candidates: HashMap<String, usize> = HashMap::new();
+
+    for cut in 0..=MAX_ENDING_LENGTH.min(word_chars.len()) {
+        let word_stem: String = word_chars[..word_chars.len() - cut].iter().collect();
+        if word_stem.chars().count() < 2 {
+            break;
+        }
+
+        for stem_orth in index.tree.find(&word_stem, MAX_STEM_DISTANCE) {
+            let mut endings: Vec<&str> = Vec::new();
+
+            if index.unique_words.contains(stem_orth) {
+                endings.push("");
+            }
+
+            for stem in index.stems.get(stem_orth).into_iter().flatten() {
+                endings.extend(
+                    get_latin_inflections()
+                        .iter()
+                        .filter(|inflection| ending_fits_stem(inflection, stem))
+                        .map(|inflection| inflection.ending.as_str()),
+                );
+            }
+
+            for ending in endings {
+                let candidate = format!("{stem_orth}{ending}");
+                if candidate == latin_word || candidates.contains_key(&candidate) {
+                    continue;
+                }
+
+                let distance = weighted_distance(latin_word, &candidate);
+                if distance <= MAX_DISTANCE {
+                    candidates.insert(candidate, distance);
+                }
+            }
+        }
+    }
+
+    let mut candidates: Vec<(String, usize)> = candidates.into_iter().collect();
+    candidates.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut suggestions: Vec<Suggestion> = Vec::new();
+    for (candidate, distance) in candidates {
+        if suggestions.len() >= count {
+            break;
+        }
+
+        if let Some(definitions) = parse(&candidate, true) {
+            suggestions.push(Suggestion {
+                word: candidate,
+                distance,
+                definitions,
+            });
+        }
+    }
+
+    suggestions
+}
+
+// only endings of the right part of speech and declension or conjugation are put on a stem
+fn ending_fits_stem(inflection: &Inflection, stem: &Stem) -> bool {
+    let pos_fits = inflection.pos == stem.pos
+        || (inflection.pos == PartOfSpeech::Participle && stem.pos == PartOfSpeech::Verb);
+    if !pos_fits {
+        return false;
+    }
+
+    let inflection_n = inflection.n.as_ref().and_then(|n| n.first());
+    let stem_n = stem.n.as_ref().and_then(|n| n.first());
+
+    match (inflection_n, stem_n) {
+        (Some(inflection_n), Some(stem_n)) => {
+            inflection_n == stem_n || *inflection_n == NValue::Integer(0)
+        }
+        _ => true,
+    }
+}
+
+/**
+ * The stems and unique words of the dictionary, indexed by how they are spelled.
+ */
+struct StemIndex {
+    tree: BkTree,
+    stems: HashMap<String, Vec<&'static Stem>>,
+    unique_words: HashSet<String>,
+}
+
+static STEM_INDEX: OnceLock<StemIndex> = OnceLock::new();
+
+fn get_stem_index() -> &'static StemIndex {
+    STEM_INDEX.get_or_init(|| {
+        let mut stems: HashMap<String, Vec<&'static Stem>> = HashMap::new();
+        for stem in get_latin_stems() {
+            stems.entry(stem.orth.clone()).or_default().push(stem);
+        }
+
+        let unique_words: HashSet<String> = get_unique_latin_words()
+            .iter()
+            .map(|word| word.orth.to_lowercase())
+            .collect();
+
+        let mut tree = BkTree::new();
+        for orth in stems.keys().chain(unique_words.iter()) {
+            tree.insert(orth);
+        }
+
+        StemIndex {
+            tree,
+            stems,
+            unique_words,
+        }
+    })
+}
+
+/**
+ * A BK-tree, which finds every word within an edit distance of a word without comparing it to all of them.
+ * The children of a node are keyed by their distance to it.
+ */
+struct BkTree {
+    nodes: Vec<BkNode>,
+}
+
+struct BkNode {
+    word: String,
+    children: HashMap<usize, usize>,
+}
+
+impl BkTree {
+    fn new() -> BkTree {
+        BkTree { nodes: Vec::new() }
+    }
+
+    fn insert(&mut self, word: &str) {
+        if self.nodes.is_empty() {
+            self.nodes.push(BkNode::new(word));
+            return;
+        }
+
+        let mut current = 0;
+        loop {
+            let distance = levenshtein(&self.nodes[current].word, word);
+            if distance == 0 {
+                return;
+            }
+
+            match self.nodes[current].children.get(&distance) {
+                Some(&child) => current = child,
+                None => {
+                    let new_node = self.nodes.len();
+                    self.nodes.push(BkNode::new(word));
+                    self.nodes[current].children.insert(distance, new_node);
+                    return;
+                }
+            }
+        }
+    }
+
+    fn find(&self, word: &str, max_distance: usize) -> Vec<&str> {
+        let mut found: Vec<&str> = Vec::new();
+        if self.nodes.is_empty() {
+            return found;
+        }
+
+        let mut to_visit: Vec<usize> = vec![0];
+        while let Some(current) = to_visit.pop() {
+            let node = &self.nodes[current];
+            let distance = levenshtein(&node.word, word);
+            if distance <= max_distance {
+                found.push(&node.word);
+            }
+
+            // by the triangle inequality, only children this close can be within the distance
+            for (&child_distance, &child) in &node.children {
+                if child_distance + max_distance >= distance
+                    && child_distance <= distance + max_distance
+                {
+                    to_visit.push(child);
+                }
+            }
+        }
+
+        found
+    }
+}
+
+impl BkNode {
+    fn new(word: &str) -> BkNode {
+        BkNode {
+            word: word.to_string(),
+            children: HashMap::new(),
+        }
+    }
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+// the confusions both ways round, as characters
+fn get_confusions() -> &'static [(Vec<char>, Vec<char>)] {
+    static CONFUSION_CHARS: OnceLock<Vec<(Vec<char>, Vec<char>)>> = OnceLock::new();
+    CONFUSION_CHARS.get_or_init(|| {
+        CONFUSIONS
+            .iter()
+            .flat_map(|&(from, to)| [(from, to), (to, from)])
+            .map(|(from, to)| (from.chars().collect(), to.chars().collect()))
+            .collect()
+    })
+}
+
+/**
+ * Edit distance where common scribal and OCR mistakes cost less than other changes.
+ * ex: amicns -> amicus costs CONFUSION_COST, amicas -> amicus costs EDIT_COST
+ */
+pub fn weighted_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    let confusions = get_confusions();
+
+    for i in 0..=a.len() {
+        for j in 0..=b.len() {
+            if i == 0 && j == 0 {
+                continue;
+            }
+
+            let mut best = usize::MAX;
+            if i > 0 {
+                best = best.min(distances[i - 1][j] + EDIT_COST);
+            }
+            if j > 0 {
+                best = best.min(distances[i][j - 1] + EDIT_COST);
+            }
+            if i > 0 && j > 0 {
+                let cost = if a[i - 1] == b[j - 1] { 0 } else { EDIT_COST };
+                best = best.min(distances[i - 1][j - 1] + cost);
+            }
+
+            for (from, to) in confusions {
+                if i >= from.len()
+                    && j >= to.len()
+                    && a[i - from.len()..i] == from[..]
+                    && b[j - to.len()..j] == to[..]
+                {
+                    best = best.min(distances[i - from.len()][j - to.len()] + CONFUSION_COST);
+                }
+            }
+
+            distances[i][j] = best;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
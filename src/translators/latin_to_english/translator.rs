@@ -1,22 +1,16 @@
 use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
-use crate::dictionary_structures::dictionary_values::{Inflection, LatinWordInfo, Part, Stem};
+use crate::dictionary_structures::dictionary_values::{Inflection, Part, Stem};
 use crate::translators::latin_to_english::utils::add_stem_to_word;
 use crate::translators::latin_to_english::LatinTranslationInfo;
-use crate::utils::data::get_latin_dictionary;
-use std::collections::HashMap;
+use crate::utils::data::get_latin_dictionary_by_id;
 
 pub fn lookup_stems(
     stems: Vec<Stem>,
     inflections: Vec<Inflection>,
 ) -> Option<Vec<LatinTranslationInfo>> {
-    let latin_dictionary = get_latin_dictionary();
+    let latin_words_map = get_latin_dictionary_by_id();
     let mut output: Vec<LatinTranslationInfo> = Vec::new();
 
-    let latin_words_map: HashMap<i32, &LatinWordInfo> = latin_dictionary
-        .iter()
-        .map(|word| (word.id, word))
-        .collect();
-
     for stem in stems {
         let dict_word = latin_words_map.get(&stem.wid);
 
@@ -2,20 +2,88 @@ pub mod trick_lists;
 pub mod word_mods;
 
 use crate::translators::latin_to_english::tricks::trick_lists::{
-    get_any_tricks, match_slur_trick_list, match_tricks_list, Trick,
+    get_any_tricks, get_medieval_tricks, get_syncopes, match_slur_trick_list, match_tricks_list,
+    Trick,
 };
 use crate::translators::latin_to_english::tricks::word_mods::{
-    double_consonants, flip, flip_flop, internal,
+    double_each_consonant, ending, flip, flip_flop, internal, internal_variants, last_internal,
 };
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
 
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Operation {
     FlipFlop,
     Flip,
     Internal,
+    LastInternal,
+    Ending,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrickKind {
+    Flip,
+    FlipFlop,
+    Internal,
+    Syncope,
+    Medieval,
+    Enclitic,
+    Prefix,
+    Suffix,
+}
+
+/**
+ * One change made to the word while looking it up, steps are kept in the order they were made.
+ * `position` is the byte offset of `original` in the word before this step (the `word` of the step before it),
+ * and `word` is the word after this step. The cost of a step says how unlikely the change is.
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TrickStep {
+    pub kind: TrickKind,
+    pub original: String,
+    pub replacement: String,
+    pub position: Option<usize>,
+    pub word: String,
+    pub explanation: String,
+    pub cost: usize,
+}
+
+impl TrickStep {
+    pub fn new(
+        kind: TrickKind,
+        original: &str,
+        replacement: &str,
+        position: Option<usize>,
+        word: &str,
+        explanation: &str,
+    ) -> TrickStep {
+        TrickStep {
+            kind,
+            original: original.to_string(),
+            replacement: replacement.to_string(),
+            position,
+            word: word.to_string(),
+            explanation: explanation.to_string(),
+            cost: 1,
+        }
+    }
+
+    pub fn with_cost(mut self, cost: usize) -> TrickStep {
+        self.cost = cost;
+        self
+    }
+}
+
+impl Display for TrickStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.explanation)
+    }
 }
 
 pub enum TrickResult {
-    Found(String, Vec<String>),
+    Found(String, Vec<TrickStep>),
     NotFound,
 }
 
@@ -34,99 +102,221 @@ impl TrickResult {
         }
     }
 
-    pub fn get_explanations(&self) -> Vec<String> {
+    pub fn get_steps(&self) -> Vec<TrickStep> {
         match self {
-            TrickResult::Found(_, explanations) => explanations.to_vec(),
+            TrickResult::Found(_, steps) => steps.to_vec(),
             TrickResult::NotFound => Vec::new(),
         }
     }
+
+    pub fn get_cost(&self) -> usize {
+        self.get_steps().iter().map(|step| step.cost).sum()
+    }
+
+    pub fn get_explanations(&self) -> Vec<String> {
+        self.get_steps()
+            .iter()
+            .map(|step| step.explanation.to_string())
+            .collect()
+    }
 }
 
-pub fn try_tricks(word: &str) -> TrickResult {
-    let trick_chars = [
-        'a', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 's', 't', 'u', 'y',
-        'z',
-    ];
-    let slur_trick_chars = ['a', 'c', 'i', 'n', 'o', 'q', 's'];
-    let first_char = word.chars().next().unwrap();
-
-    if trick_chars.contains(&first_char) {
-        let trick_list = match_tricks_list(first_char);
-        let (new_word, explanations) = iterate_over_tricks(&trick_list, word);
-        if new_word != word {
-            return TrickResult::Found(new_word, explanations);
-        }
-    } else {
-        let any_tricks = get_any_tricks();
-        let (new_word, explanations) = iterate_over_tricks(&any_tricks, word);
+// the most tricks applied to a single word
+const MAX_TRICK_DEPTH: usize = 2;
+// the most words the tricks can give, as each one has to be parsed
+const MAX_TRICK_CANDIDATES: usize = 64;
+
+/**
+ * Tries every trick that fits the word, then every trick that fits each of those words, up to MAX_TRICK_DEPTH tricks.
+ * A word is only given once, by the first combination that reaches it, so a later trick can not undo an earlier one.
+ * Results are sorted by cost, cheapest first.
+ */
+pub fn try_tricks(word: &str) -> Vec<TrickResult> {
+    let mut results: Vec<TrickResult> = Vec::new();
+    let mut seen_words: Vec<String> = vec![word.to_string()];
+    let mut current_level: Vec<(String, Vec<TrickStep>)> = vec![(word.to_string(), Vec::new())];
+
+    for _ in 0..MAX_TRICK_DEPTH {
+        let mut next_level: Vec<(String, Vec<TrickStep>)> = Vec::new();
 
-        if new_word == word && slur_trick_chars.contains(&first_char) {
-            let trick_list = match_slur_trick_list(first_char);
-            let (updated_new_word, updated_explanations) = iterate_over_tricks(&trick_list, word);
+        for (current_word, steps) in &current_level {
+            for trick in tricks_for_word(current_word) {
+                let trick_steps = apply_trick(trick, current_word);
+                let new_word = match trick_steps.last() {
+                    Some(step) => step.word.clone(),
+                    None => continue,
+                };
 
-            if updated_new_word != word {
-                return TrickResult::Found(updated_new_word, updated_explanations);
+                if seen_words.contains(&new_word) || seen_words.len() > MAX_TRICK_CANDIDATES {
+                    continue;
+                }
+                seen_words.push(new_word.clone());
+
+                let mut new_steps = steps.clone();
+                new_steps.extend(trick_steps);
+                results.push(TrickResult::Found(new_word.clone(), new_steps.clone()));
+                next_level.push((new_word, new_steps));
             }
-        } else if new_word != word {
-            return TrickResult::Found(new_word, explanations);
         }
+
+        current_level = next_level;
     }
 
-    TrickResult::NotFound
+    results.sort_by_key(|result| result.get_cost());
+    results
 }
 
-pub fn try_syncopes(word: &str) -> TrickResult {
-    let mut new_word = word.to_string();
-    let mut explanation = String::new();
+// the tricks for the first letter of the word, or the general ones if the letter has none
+fn tricks_for_word(word: &str) -> Vec<&'static Trick> {
+    let first_char = match word.chars().next() {
+        Some(first_char) => first_char,
+        None => return Vec::new(),
+    };
 
-    if new_word.len() >= 3 && new_word.ends_with("ivi") {
-        new_word.replace_range(new_word.len() - 3..new_word.len(), "ii");
-        explanation =
-            String::from("Syncopated perfect 'ivi' can drop 'v' without contracting vowel");
-    } else if new_word.len() >= 4 && new_word.ends_with("iver") {
-        new_word.replace_range(new_word.len() - 4..new_word.len(), "ier");
-        explanation =
-            String::from("Syncopated perfect 'iver' can drop 'v' without contracting vowel");
+    if let Some(trick_list) = match_tricks_list(first_char) {
+        return trick_list.iter().collect();
     }
 
-    if new_word != word {
-        return TrickResult::Found(new_word.to_string(), vec![explanation]);
+    let mut trick_list: Vec<&'static Trick> = get_any_tricks().iter().collect();
+    if let Some(slur_trick_list) = match_slur_trick_list(first_char) {
+        trick_list.extend(slur_trick_list.iter());
     }
 
-    TrickResult::NotFound
+    trick_list
 }
 
-pub fn try_medieval_tricks(word: &str) -> TrickResult {
-    let new_word = word.to_string();
+/**
+ * Expands contracted perfect forms and poetic endings (amasse -> amavisse, amavere -> amaverunt).
+ * Every rule that fits gives its own result, as more than one can fit the same word.
+ */
+pub fn try_syncopes(word: &str) -> Vec<TrickResult> {
+    let mut results: Vec<TrickResult> = Vec::new();
+
+    for syncope in get_syncopes() {
+        let mut steps = apply_trick(syncope, word);
+        let new_word = match steps.last() {
+            Some(step) => step.word.clone(),
+            None => continue,
+        };
 
-    let (updated_word, updated_explanation) = double_consonants(&new_word);
-    if updated_word != new_word {
-        return TrickResult::Found(updated_word, vec![updated_explanation]);
+        for step in steps.iter_mut() {
+            step.kind = TrickKind::Syncope;
+        }
+        results.push(TrickResult::Found(new_word, steps));
     }
 
-    TrickResult::NotFound
+    results
 }
 
-fn iterate_over_tricks(trick_list: &[Trick], word: &str) -> (String, Vec<String>) {
-    // word should be modified after each operation is applied.
-    let mut explanations = Vec::new();
-    let mut modified_word = word.to_string();
-
-    for trick in trick_list.iter() {
-        let (word, new_explanation) = match trick.operation {
-            Operation::FlipFlop => flip_flop(trick.str_1, trick.str_2, word),
-            Operation::Flip => flip(trick.str_1, trick.str_2, word),
-            Operation::Internal => internal(trick.str_1, trick.str_2, word),
-        };
+/**
+ * Gives every spelling the word could have in classical Latin, if it was written with medieval spelling.
+ * Each change is tried on its own, so that one variant does not undo another.
+ */
+pub fn try_medieval_spellings(word: &str) -> Vec<TrickResult> {
+    let mut variants: Vec<TrickStep> = Vec::new();
 
-        if word != modified_word {
-            modified_word = word;
+    for trick in get_medieval_tricks().iter() {
+        if trick.operation == Operation::Internal {
+            variants.extend(internal_variants(&trick.from, &trick.to, word));
+            continue;
         }
 
-        if !new_explanation.is_empty() {
-            explanations.push(new_explanation);
+        variants.extend(apply_trick(trick, word));
+    }
+
+    variants.extend(double_each_consonant(word));
+
+    let mut results: Vec<TrickResult> = Vec::new();
+    let mut seen_words: Vec<String> = vec![word.to_string()];
+
+    for mut step in variants {
+        if seen_words.contains(&step.word) {
+            continue;
         }
+
+        seen_words.push(step.word.clone());
+        step.kind = TrickKind::Medieval;
+        step.explanation = format!("Medieval spelling: {}", step.explanation);
+        results.push(TrickResult::Found(step.word.to_string(), vec![step]));
+    }
+
+    results
+}
+
+/**
+ * Applies a single trick to the word, giving a step for each place it changed.
+ * An internal trick changes every place the letters are found, the cost of the trick is only counted once.
+ * Gives nothing if the trick does not fit the word.
+ */
+pub fn apply_trick(trick: &Trick, word: &str) -> Vec<TrickStep> {
+    let (new_word, explanation) = match trick.operation {
+        Operation::FlipFlop => flip_flop(&trick.from, &trick.to, word),
+        Operation::Flip => flip(&trick.from, &trick.to, word),
+        Operation::Internal => internal(&trick.from, &trick.to, word),
+        Operation::LastInternal => last_internal(&trick.from, &trick.to, word),
+        Operation::Ending => ending(&trick.from, &trick.to, word),
+    };
+
+    if new_word == word || explanation.is_empty() {
+        return Vec::new();
+    }
+
+    let explanation = match &trick.explanation {
+        Some(trick_explanation) => trick_explanation.to_string(),
+        None => explanation,
+    };
+
+    let (kind, positions) = match trick.operation {
+        Operation::FlipFlop => (TrickKind::FlipFlop, vec![0]),
+        Operation::Flip => (TrickKind::Flip, vec![0]),
+        Operation::Internal => (
+            TrickKind::Internal,
+            word.match_indices(&trick.from)
+                .map(|(index, _)| index)
+                .collect(),
+        ),
+        Operation::LastInternal => (
+            TrickKind::Internal,
+            word.rfind(&trick.from).into_iter().collect(),
+        ),
+        Operation::Ending => (TrickKind::Internal, vec![word.len() - trick.from.len()]),
+    };
+
+    if positions.len() < 2 {
+        return vec![TrickStep::new(
+            kind,
+            &trick.from,
+            &trick.to,
+            positions.first().copied(),
+            &new_word,
+            &explanation,
+        )
+        .with_cost(trick.cost.unwrap_or(1))];
+    }
+
+    // each occurrence is its own step, with the word as it is after that replacement
+    let mut steps: Vec<TrickStep> = Vec::new();
+    let mut current_word = word.to_string();
+    let mut shift: isize = 0;
+
+    for (i, index) in positions.iter().enumerate() {
+        let position = (*index as isize + shift) as usize;
+        current_word.replace_range(position..position + trick.from.len(), &trick.to);
+        shift += trick.to.len() as isize - trick.from.len() as isize;
+
+        let cost = if i == 0 { trick.cost.unwrap_or(1) } else { 0 };
+        steps.push(
+            TrickStep::new(
+                kind,
+                &trick.from,
+                &trick.to,
+                Some(position),
+                &current_word,
+                &explanation,
+            )
+            .with_cost(cost),
+        );
     }
 
-    (modified_word, explanations)
+    steps
 }
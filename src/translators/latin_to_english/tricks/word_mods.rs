@@ -1,3 +1,4 @@
+use crate::translators::latin_to_english::tricks::{TrickKind, TrickStep};
 use crate::utils::is_vowel;
 
 pub fn flip(str_to_replace: &str, replacement_str: &str, word: &str) -> (String, String) {
@@ -16,9 +17,7 @@ pub fn flip(str_to_replace: &str, replacement_str: &str, word: &str) -> (String,
             #[allow(clippy::const_is_empty)]
             {
                 explanation = if !canned_explanation.is_empty() {
-                    format!(
-                        "An initial '{str_to_replace}{canned_explanation}{replacement_str}'"
-                    )
+                    format!("An initial '{str_to_replace}{canned_explanation}{replacement_str}'")
                 } else {
                     String::from("")
                 };
@@ -42,9 +41,8 @@ pub fn flip_flop(str_to_replace: &str, replacement_str: &str, word: &str) -> (St
         new_word.push_str(&word[str_to_replace.len()..]);
 
         if new_word.len() >= replacement_str.len() + 2 && new_word.starts_with(replacement_str) {
-            let explanation = format!(
-                "An initial '{str_to_replace}' may be rendered by '{replacement_str}'"
-            );
+            let explanation =
+                format!("An initial '{str_to_replace}' may be rendered by '{replacement_str}'");
 
             return (new_word, explanation);
         }
@@ -61,9 +59,8 @@ pub fn internal(str_to_replace: &str, replacement_str: &str, word: &str) -> (Str
         let new_word = word.replace(str_to_replace, replacement_str);
 
         if new_word.len() >= replacement_str.len() + 2 {
-            let explanation = format!(
-                "An internal '{str_to_replace}' may be rendered by '{replacement_str}'"
-            );
+            let explanation =
+                format!("An internal '{str_to_replace}' may be rendered by '{replacement_str}'");
 
             return (new_word, explanation);
         }
@@ -72,51 +69,115 @@ pub fn internal(str_to_replace: &str, replacement_str: &str, word: &str) -> (Str
     (word.to_string(), explanation)
 }
 
-pub fn double_consonants(latin_word: &str) -> (String, String) {
-    let mut doubled_word = String::new();
-    let mut explanation = String::new();
+// replaces only the last str_to_replace in the word, which can not be at its start
+pub fn last_internal(str_to_replace: &str, replacement_str: &str, word: &str) -> (String, String) {
+    match word.rfind(str_to_replace) {
+        Some(index) if index > 0 && !str_to_replace.is_empty() => {
+            let mut new_word = String::from(word);
+            new_word.replace_range(index..index + str_to_replace.len(), replacement_str);
+            let explanation =
+                format!("An internal '{str_to_replace}' may be rendered by '{replacement_str}'");
 
-    let split_word: Vec<char> = latin_word.chars().collect();
+            (new_word, explanation)
+        }
+        _ => (word.to_string(), String::new()),
+    }
+}
+
+// replaces str_to_replace at the end of the word, which can not be the whole word
+pub fn ending(str_to_replace: &str, replacement_str: &str, word: &str) -> (String, String) {
+    if word.len() > str_to_replace.len() && word.ends_with(str_to_replace) {
+        let mut new_word = String::from(&word[..word.len() - str_to_replace.len()]);
+        new_word.push_str(replacement_str);
+        let explanation =
+            format!("A final '{str_to_replace}' may be rendered by '{replacement_str}'");
 
-    if split_word.len() == 1 {
-        return (latin_word.to_string(), explanation);
+        return (new_word, explanation);
     }
 
-    for (i, letter) in split_word.iter().enumerate() {
-        if is_vowel(letter.to_owned()) {
-            doubled_word.push(*letter);
-            continue;
+    (word.to_string(), String::new())
+}
+
+// like internal, but gives one word for each single occurrence of str_to_replace, as well as the word with all of them replaced
+pub fn internal_variants(
+    str_to_replace: &str,
+    replacement_str: &str,
+    word: &str,
+) -> Vec<TrickStep> {
+    let mut variants: Vec<TrickStep> = Vec::new();
+
+    if str_to_replace.is_empty() {
+        return variants;
+    }
+
+    let explanation = if replacement_str.is_empty() {
+        format!("An internal '{str_to_replace}' may be dropped")
+    } else {
+        format!("An internal '{str_to_replace}' may be rendered by '{replacement_str}'")
+    };
+
+    for (index, _) in word.match_indices(str_to_replace) {
+        let mut new_word = String::from(word);
+        new_word.replace_range(index..index + str_to_replace.len(), replacement_str);
+
+        if new_word.len() >= replacement_str.len() + 2
+            && !variants.iter().any(|variant| variant.word == new_word)
+        {
+            variants.push(TrickStep::new(
+                TrickKind::Internal,
+                str_to_replace,
+                replacement_str,
+                Some(index),
+                &new_word,
+                &explanation,
+            ));
         }
-        if i == 0 {
-            if is_vowel(split_word[i + 1]) {
-                doubled_word.push(*letter);
-                doubled_word.push(*letter);
-                continue;
-            } else {
-                doubled_word.push(*letter);
-                continue;
-            }
-        } else if i == split_word.len() - 1 {
-            if is_vowel(split_word[i - 1]) {
-                doubled_word.push(*letter);
-                doubled_word.push(*letter);
-                continue;
-            } else {
-                doubled_word.push(*letter);
-                continue;
-            }
-        } else if is_vowel(split_word[i - 1]) && is_vowel(split_word[i + 1]) {
-            doubled_word.push(*letter);
-            doubled_word.push(*letter);
-            continue;
-        } else {
-            doubled_word.push(*letter);
+    }
+
+    if variants.len() > 1 {
+        let (new_word, _) = internal(str_to_replace, replacement_str, word);
+        if !variants.iter().any(|variant| variant.word == new_word) {
+            variants.push(TrickStep::new(
+                TrickKind::Internal,
+                str_to_replace,
+                replacement_str,
+                word.find(str_to_replace),
+                &new_word,
+                &format!(
+                    "Every internal '{str_to_replace}' may be rendered by '{replacement_str}'"
+                ),
+            ));
         }
     }
 
-    if doubled_word.len() > latin_word.len() {
-        explanation = format!("Consonants may be doubled in '{latin_word}'");
+    variants
+}
+
+// doubles one consonant standing between two vowels at a time
+pub fn double_each_consonant(latin_word: &str) -> Vec<TrickStep> {
+    let mut variants: Vec<TrickStep> = Vec::new();
+    let split_word: Vec<char> = latin_word.chars().collect();
+
+    for i in 1..split_word.len().saturating_sub(1) {
+        let letter = split_word[i];
+        if is_vowel(letter) || !is_vowel(split_word[i - 1]) || !is_vowel(split_word[i + 1]) {
+            continue;
+        }
+
+        let mut doubled_word: String = split_word[..=i].iter().collect();
+        let position = doubled_word.len() - letter.len_utf8();
+        doubled_word.push(letter);
+        doubled_word.extend(&split_word[i + 1..]);
+
+        variants.push(TrickStep::new(
+            TrickKind::Internal,
+            &letter.to_string(),
+            &format!("{letter}{letter}"),
+            Some(position),
+            &doubled_word,
+            &format!("A single '{letter}' may be rendered by '{letter}{letter}'"),
+        ));
     }
 
-    (doubled_word, explanation)
+    variants
 }
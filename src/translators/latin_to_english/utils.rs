@@ -1,9 +1,13 @@
-use crate::dictionary_structures::dictionary_values::{Modifier, ModifierType, Stem};
-use crate::translators::latin_to_english::parser::find_form;
+use crate::dictionary_structures::dictionary_keys::PartOfSpeech;
+use crate::dictionary_structures::dictionary_values::{
+    Attachment, Inflection, Modifier, ModifierType, NValue, Stem,
+};
+use crate::translators::latin_to_english::parser::{find_form, find_suffixed_form, suffixed_bases};
+use crate::translators::latin_to_english::tricks::{TrickKind, TrickStep};
 use crate::translators::latin_to_english::LatinTranslationInfo;
 use crate::utils::data::{
-    get_latin_not_packons, get_latin_packons, get_latin_prefixes, get_latin_suffixes,
-    get_latin_tackons,
+    get_latin_assimilations, get_latin_not_packons, get_latin_packons, get_latin_prefixes,
+    get_latin_suffixes, get_latin_tackons, get_latin_tickons,
 };
 
 pub fn add_stem_to_word(matched_stem: Stem, matching_word: Option<&mut LatinTranslationInfo>) {
@@ -17,112 +21,535 @@ pub fn add_stem_to_word(matched_stem: Stem, matching_word: Option<&mut LatinTran
     }
 }
 
-pub fn reduce(latin_word: &str) -> Option<Vec<LatinTranslationInfo>> {
-    let mut modifiers: Vec<Modifier> = Vec::new();
-    let latin_prefixes = get_latin_prefixes();
-    let latin_suffixes = get_latin_suffixes();
+/**
+ * The parts of speech a suffix changes a word between, read from its form.
+ * ex: "N 2 ADJ 1 1 POS 0" attaches to a noun and makes an adjective of the 1st and 2nd declension.
+ * The stem keys in the form are not used, as latin_stems.json does not say which stem of its word a stem is.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuffixForm {
+    pub source_pos: PartOfSpeech,
+    pub target_pos: PartOfSpeech,
+    pub target_n: Option<Vec<NValue>>,
+    pub target_form: String,
+}
+
+impl SuffixForm {
+    pub fn from_form(form: &str) -> Option<SuffixForm> {
+        let parts: Vec<&str> = form.split_whitespace().collect();
+        if parts.len() < 4 {
+            return None;
+        }
 
-    let mut stripped_latin_word = latin_word.to_string();
-    latin_prefixes.iter().for_each(|prefix| {
-        if stripped_latin_word.starts_with(prefix.orth.as_str()) {
-            stripped_latin_word = stripped_latin_word
-                .trim_start_matches(prefix.orth.as_str())
-                .to_string();
+        let target_pos = PartOfSpeech::dict_key_to_part_of_speech(parts[2]);
+        if target_pos == PartOfSpeech::Unknown {
+            return None;
+        }
 
-            let mut modifier = Modifier::new();
-            modifier.set_orth(&prefix.orth);
-            modifier.set_pos(prefix.pos);
-            modifier.set_senses(&prefix.senses);
-            modifier.set_modifier(ModifierType::Prefix);
+        // the target form ends with the key of the new stem
+        let target_parts = &parts[2..parts.len() - 1];
+        let target_n: Vec<NValue> = target_parts[1..]
+            .iter()
+            .map_while(|part| part.parse::<i8>().ok())
+            .map(NValue::Integer)
+            .collect();
 
-            modifiers.push(modifier);
+        Some(SuffixForm {
+            // X: attaches to any part of speech
+            source_pos: PartOfSpeech::dict_key_to_part_of_speech(parts[0]),
+            target_pos,
+            target_n: if target_n.is_empty() {
+                None
+            } else {
+                Some(target_n)
+            },
+            target_form: target_parts.join(" "),
+        })
+    }
+
+    pub fn fits_stem(&self, stem: &Stem) -> bool {
+        self.source_pos == PartOfSpeech::Unknown
+            || stem.pos == self.source_pos
+            || (self.source_pos == PartOfSpeech::Verb && stem.pos == PartOfSpeech::Participle)
+    }
+
+    pub fn fits_inflection(&self, inflection: &Inflection) -> bool {
+        if inflection.pos != self.target_pos {
+            return false;
+        }
+
+        match (&self.target_n, &inflection.n) {
+            (Some(target_n), Some(inflection_n)) => {
+                target_n.iter().zip(inflection_n).all(|(target, found)| {
+                    target == found || *target == NValue::Integer(0) || *found == NValue::Integer(0)
+                })
+            }
+            _ => true,
         }
+    }
+}
+
+// some suffixes are followed by the letter they connect with (ex: "er  t"), only the first part is written in the word
+fn suffix_orth(suffix: &Modifier) -> &str {
+    suffix.orth.split_whitespace().next().unwrap_or("")
+}
+
+/**
+ * A prefix found at the start of a word, as it is written there (ad is written af in affero).
+ */
+#[derive(Clone)]
+struct PrefixMatch {
+    prefix: &'static Modifier,
+    surface: String,
+    explanation: Option<String>,
+}
+
+/**
+ * One way of splitting a word into a prefix, what is left of the word, and a suffix.
+ * Following ADDONS.LAT, a word has at most one prefix and one suffix (its addons are removed by split_enclitics).
+ */
+struct Decomposition {
+    prefix: Option<PrefixMatch>,
+    suffix: Option<(&'static Modifier, SuffixForm)>,
+    stripped_word: String,
+    restored_vowel: Option<RestoredVowel>,
+}
+
+/**
+ * The first vowel of a word after a prefix, put back to the vowel of the simple word (col + ligo -> lego).
+ */
+#[derive(Clone)]
+struct RestoredVowel {
+    position: usize,
+    weakened: &'static str,
+    original: &'static str,
+}
+
+// the vowels of a simple word are often weakened after a prefix (lego -> colligo, facio -> conficio, factus -> confectus)
+const WEAKENED_VOWELS: [(&str, &str); 3] = [("i", "e"), ("i", "a"), ("e", "a")];
+
+impl Decomposition {
+    fn affix_count(&self) -> usize {
+        self.prefix.is_some() as usize + self.suffix.is_some() as usize
+    }
+
+    fn affix_len(&self) -> usize {
+        self.prefix
+            .as_ref()
+            .map_or(0, |prefix| prefix.surface.len())
+            + self
+                .suffix
+                .as_ref()
+                .map_or(0, |(suffix, _)| suffix_orth(suffix).len())
+    }
+}
+
+/**
+ * Tries every way of removing a prefix and / or a suffix from the word, and gives all the words that can be found.
+ * Words found with fewer and then longer affixes come first.
+ */
+pub fn reduce(latin_word: &str) -> Option<Vec<LatinTranslationInfo>> {
+    let mut decompositions = decompose(latin_word);
+    decompositions.sort_by(|a, b| {
+        a.affix_count()
+            .cmp(&b.affix_count())
+            .then(b.affix_len().cmp(&a.affix_len()))
+            .then(a.restored_vowel.is_some().cmp(&b.restored_vowel.is_some()))
     });
 
-    latin_suffixes.iter().for_each(|suffix| {
-        if stripped_latin_word.ends_with(suffix.orth.as_str()) {
-            stripped_latin_word = stripped_latin_word
-                .trim_end_matches(suffix.orth.as_str())
-                .to_string();
+    let mut output: Vec<LatinTranslationInfo> = Vec::new();
 
-            let mut modifier = Modifier::new();
-            modifier.set_orth(&suffix.orth);
-            modifier.set_pos(suffix.pos);
-            modifier.set_senses(&suffix.senses);
-            modifier.set_modifier(ModifierType::Suffix);
+    for decomposition in decompositions {
+        let found_words = match &decomposition.suffix {
+            Some((suffix, suffix_form)) => find_suffixed_form(
+                &decomposition.stripped_word,
+                suffix_orth(suffix),
+                suffix_form,
+            ),
+            None => find_form(&decomposition.stripped_word, true),
+        };
+        let found_words = match found_words {
+            Some(found_words) => found_words,
+            None => continue,
+        };
 
-            modifiers.push(modifier);
+        for mut word in found_words {
+            let already_found = output
+                .iter()
+                .any(|x| x.word.id == word.word.id && x.word.orth == word.word.orth);
+            if already_found {
+                continue;
+            }
+
+            let (modifiers, steps) =
+                decomposition_modifiers(latin_word, &decomposition, &word.stem.orth);
+            word.word.set_modifiers(modifiers);
+            word.set_tricks(&steps);
+            output.push(word);
         }
-    });
+    }
 
-    if stripped_latin_word == latin_word || stripped_latin_word.is_empty() || modifiers.is_empty() {
-        return None;
+    if output.is_empty() {
+        None
+    } else {
+        Some(output)
     }
+}
+
+// some prefixes are followed by the letter they are used before (ex: "af f"), only the first part is written in the word
+fn split_prefix_orth(prefix: &Modifier) -> (&str, Option<&str>) {
+    let mut parts = prefix.orth.split_whitespace();
+    (parts.next().unwrap_or(""), parts.next())
+}
+
+/**
+ * Every prefix the word can start with, including the ones changed to fit the letter after them.
+ */
+fn find_prefixes(latin_word: &str) -> Vec<PrefixMatch> {
+    let latin_prefixes = get_latin_prefixes();
+    let assimilations = get_latin_assimilations();
+    let mut prefixes: Vec<PrefixMatch> = Vec::new();
 
-    let mut output = find_form(&stripped_latin_word, true);
+    for assimilation in assimilations {
+        let rest = match latin_word.strip_prefix(&assimilation.form) {
+            Some(rest) => rest,
+            None => continue,
+        };
+        let before = match assimilation
+            .before
+            .iter()
+            .find(|before| rest.starts_with(before.as_str()))
+        {
+            Some(before) => before,
+            None => continue,
+        };
 
-    #[allow(clippy::unnecessary_unwrap)]
-    if output.is_some() {
-        for word in output.as_mut().unwrap() {
-            word.word.set_modifiers(modifiers.clone());
+        for prefix in latin_prefixes
+            .iter()
+            .filter(|prefix| prefix.orth == assimilation.prefix)
+        {
+            prefixes.push(PrefixMatch {
+                prefix,
+                surface: assimilation.form.clone(),
+                explanation: Some(format!(
+                    "The prefix '{}' is written '{}' before '{before}'",
+                    assimilation.prefix, assimilation.form
+                )),
+            });
         }
+    }
 
-        output
-    } else {
-        None
+    for prefix in latin_prefixes {
+        let (surface, before) = split_prefix_orth(prefix);
+        if surface.is_empty() || !latin_word.starts_with(surface) {
+            continue;
+        }
+
+        let explanation = match before {
+            Some(before) => {
+                if !latin_word[surface.len()..].starts_with(before) {
+                    continue;
+                }
+
+                // already found as an assimilated prefix, with the prefix it comes from
+                let assimilated = assimilations.iter().any(|assimilation| {
+                    assimilation.form == surface && assimilation.before.iter().any(|x| x == before)
+                });
+                if assimilated {
+                    continue;
+                }
+
+                Some(format!("The prefix '{surface}' is used before '{before}'"))
+            }
+            None => None,
+        };
+
+        prefixes.push(PrefixMatch {
+            prefix,
+            surface: surface.to_string(),
+            explanation,
+        });
+    }
+
+    // the lists have some prefixes more than once
+    let mut unique_prefixes: Vec<PrefixMatch> = Vec::new();
+    for prefix in prefixes {
+        let already_added = unique_prefixes.iter().any(|x| {
+            x.surface == prefix.surface
+                && x.prefix.senses == prefix.prefix.senses
+                && x.prefix.pos == prefix.prefix.pos
+        });
+        if !already_added {
+            unique_prefixes.push(prefix);
+        }
+    }
+
+    unique_prefixes
+}
+
+fn decompose(latin_word: &str) -> Vec<Decomposition> {
+    let mut decompositions: Vec<Decomposition> = Vec::new();
+
+    let mut prefixes: Vec<Option<PrefixMatch>> = vec![None];
+    prefixes.extend(find_prefixes(latin_word).into_iter().map(Some));
+
+    for prefix in prefixes {
+        let without_prefix =
+            &latin_word[prefix.as_ref().map_or(0, |prefix| prefix.surface.len())..];
+        if without_prefix.is_empty() {
+            continue;
+        }
+
+        let mut suffixes: Vec<Option<(&'static Modifier, SuffixForm)>> = vec![None];
+        for suffix in get_latin_suffixes() {
+            let orth = suffix_orth(suffix);
+            if orth.is_empty() || !without_prefix.contains(orth) {
+                continue;
+            }
+
+            // the suffix has to come just before an ending it can take
+            let suffix_form = suffix
+                .form
+                .as_ref()
+                .and_then(|form| SuffixForm::from_form(&form.as_str()))
+                .filter(|suffix_form| {
+                    !suffixed_bases(without_prefix, orth, suffix_form).is_empty()
+                });
+            if let Some(suffix_form) = suffix_form {
+                // the list has some suffixes more than once
+                if !suffixes.iter().flatten().any(|(x, form)| {
+                    suffix_orth(x) == orth && x.senses == suffix.senses && *form == suffix_form
+                }) {
+                    suffixes.push(Some((suffix, suffix_form)));
+                }
+            }
+        }
+
+        for suffix in suffixes {
+            if prefix.is_none() && suffix.is_none() {
+                continue;
+            }
+
+            if prefix.is_some() {
+                for restored_vowel in restored_vowels(without_prefix) {
+                    let mut stripped_word = without_prefix.to_string();
+                    stripped_word.replace_range(
+                        restored_vowel.position
+                            ..restored_vowel.position + restored_vowel.weakened.len(),
+                        restored_vowel.original,
+                    );
+
+                    decompositions.push(Decomposition {
+                        prefix: prefix.clone(),
+                        suffix: suffix.clone(),
+                        stripped_word,
+                        restored_vowel: Some(restored_vowel),
+                    });
+                }
+            }
+
+            decompositions.push(Decomposition {
+                prefix: prefix.clone(),
+                suffix,
+                stripped_word: without_prefix.to_string(),
+                restored_vowel: None,
+            });
+        }
     }
+
+    decompositions
 }
 
-pub fn split_enclitic(latin_word: &str) -> (String, Vec<Modifier>) {
-    let mut modifiers: Vec<Modifier> = Vec::new();
-    let latin_not_packons = get_latin_not_packons();
-    let mut split_word = latin_word.to_string();
-    let latin_tackons = get_latin_tackons();
-    let latin_packons = get_latin_packons();
+// the ways the first vowel of the word could have been weakened after a prefix
+fn restored_vowels(word: &str) -> Vec<RestoredVowel> {
+    let position = match word.find(|letter: char| "aeiouy".contains(letter)) {
+        Some(position) => position,
+        None => return Vec::new(),
+    };
 
-    let tackon = latin_tackons
+    WEAKENED_VOWELS
         .iter()
-        .find(|tackon| split_word.ends_with(tackon.orth.as_str()));
-
-    if let Some(tackon) = tackon {
-        // Est exception
-        if latin_word != "est" {
-            let mut modifier = Modifier::new();
-            modifier.set_orth(&tackon.orth);
-            modifier.set_pos(tackon.pos);
-            modifier.set_senses(&tackon.senses);
-            modifier.set_modifier(ModifierType::Tackon);
-
-            split_word.truncate(split_word.len() - tackon.orth.len());
-            modifiers.push(modifier);
-        }
-    } else if latin_word.starts_with("qu") {
-        for packon in latin_packons {
-            if split_word.ends_with(packon.orth.as_str()) {
-                let mut modifier = Modifier::new();
-                modifier.set_orth(&packon.orth);
-                modifier.set_pos(packon.pos);
-                modifier.set_senses(&packon.senses);
-                modifier.set_modifier(ModifierType::Packon);
-
-                split_word.truncate(split_word.len() - packon.orth.len());
-                modifiers.push(modifier);
+        .filter(|(weakened, _)| word[position..].starts_with(weakened))
+        .map(|(weakened, original)| RestoredVowel {
+            position,
+            weakened,
+            original,
+        })
+        .collect()
+}
+
+fn decomposition_modifiers(
+    latin_word: &str,
+    decomposition: &Decomposition,
+    stem: &str,
+) -> (Vec<Modifier>, Vec<TrickStep>) {
+    let mut modifiers: Vec<Modifier> = Vec::new();
+    let mut steps: Vec<TrickStep> = Vec::new();
+    let mut word = latin_word.to_string();
+
+    if let Some(prefix_match) = &decomposition.prefix {
+        let prefix = prefix_match.prefix;
+        let (orth, _) = split_prefix_orth(prefix);
+        word = word[prefix_match.surface.len()..].to_string();
+
+        let mut modifier = Modifier::new();
+        modifier.set_orth(orth);
+        modifier.set_pos(prefix.pos);
+        modifier.set_senses(&prefix.senses);
+        modifier.set_modifier(ModifierType::Prefix);
+        modifiers.push(modifier);
+
+        let explanation = match &prefix_match.explanation {
+            Some(explanation) => {
+                format!("The prefix '{orth}' may be attached to '{word}'. {explanation}")
             }
+            None => format!("The prefix '{orth}' may be attached to '{word}'"),
+        };
+        steps.push(TrickStep::new(
+            TrickKind::Prefix,
+            &prefix_match.surface,
+            "",
+            Some(0),
+            &word,
+            &explanation,
+        ));
+    }
+
+    if let Some(restored_vowel) = &decomposition.restored_vowel {
+        let position = restored_vowel.position;
+        word.replace_range(
+            position..position + restored_vowel.weakened.len(),
+            restored_vowel.original,
+        );
+
+        steps.push(TrickStep::new(
+            TrickKind::Prefix,
+            restored_vowel.weakened,
+            restored_vowel.original,
+            Some(position),
+            &word,
+            &format!(
+                "After a prefix, '{}' may be weakened to '{}'",
+                restored_vowel.original, restored_vowel.weakened
+            ),
+        ));
+    }
+
+    if let Some((suffix, suffix_form)) = &decomposition.suffix {
+        let orth = suffix_orth(suffix);
+
+        let mut modifier = Modifier::new();
+        modifier.set_orth(orth);
+        modifier.set_pos(suffix.pos);
+        modifier.set_senses(&suffix.senses);
+        modifier.set_modifier(ModifierType::Suffix);
+        if let Some(form) = &suffix.form {
+            modifier.set_form(form.clone());
         }
-    } else {
-        for packon in latin_not_packons {
-            if split_word.ends_with(packon.orth.as_str()) {
-                let mut modifier = Modifier::new();
-                modifier.set_orth(&packon.orth);
-                modifier.set_pos(packon.pos);
-                modifier.set_senses(&packon.senses);
-                modifier.set_modifier(ModifierType::Packon);
-
-                split_word.truncate(split_word.len() - packon.orth.len());
-                modifiers.push(modifier);
+        modifiers.push(modifier);
+
+        // the suffix comes right after the stem
+        let position = stem.len();
+        if word
+            .get(position..)
+            .is_some_and(|rest| rest.starts_with(orth))
+        {
+            word.replace_range(position..position + orth.len(), "");
+
+            steps.push(TrickStep::new(
+                TrickKind::Suffix,
+                orth,
+                "",
+                Some(position),
+                &word,
+                &format!(
+                    "The suffix '{orth}' may be attached to '{stem}', making a {}",
+                    suffix_form.target_pos.as_str()
+                ),
+            ));
+        }
+    }
+
+    (modifiers, steps)
+}
+
+fn attachment_modifier(attachment: &Attachment, modifier_type: ModifierType) -> Modifier {
+    let mut modifier = Modifier::new();
+    modifier.set_orth(&attachment.orth);
+    modifier.set_pos(attachment.pos);
+    modifier.set_senses(&attachment.senses);
+    modifier.set_modifier(modifier_type);
+    modifier
+}
+
+/**
+ * Every way of removing addons from the word, with the addons removed (in the order they were removed).
+ * A word can have a tickon at its start, then at its end one enclitic (que, ne, ve, est) after one packon or
+ * one of the other addons (ex: nobiscumque -> nobis + cum + que). The word without any addons is not given.
+ * Splits with fewer addons come first.
+ */
+pub fn split_enclitics(latin_word: &str) -> Vec<(String, Vec<Modifier>)> {
+    let mut splits: Vec<(String, Vec<Modifier>)> = vec![(latin_word.to_string(), Vec::new())];
+
+    // tickons come before qu- pronouns (si + quis, nescio + quid)
+    for tickon in get_latin_tickons() {
+        if let Some(rest) = latin_word.strip_prefix(tickon.orth.as_str()) {
+            if rest.starts_with("qu") {
+                let modifier = attachment_modifier(tickon, ModifierType::Tickon);
+                splits.push((rest.to_string(), vec![modifier]));
             }
         }
     }
 
-    (split_word, modifiers)
+    let mut with_tackons: Vec<(String, Vec<Modifier>)> = Vec::new();
+    for (split_word, modifiers) in &splits {
+        for tackon in get_latin_tackons() {
+            if let Some(rest) = split_word.strip_suffix(tackon.orth.as_str()) {
+                if rest.is_empty() {
+                    continue;
+                }
+
+                let mut new_modifiers = modifiers.clone();
+                new_modifiers.push(attachment_modifier(tackon, ModifierType::Tackon));
+                with_tackons.push((rest.to_string(), new_modifiers));
+            }
+        }
+    }
+    splits.extend(with_tackons);
+
+    let mut with_packons: Vec<(String, Vec<Modifier>)> = Vec::new();
+    for (split_word, modifiers) in &splits {
+        let (packons, modifier_type) = if split_word.starts_with("qu") {
+            (get_latin_packons(), ModifierType::Packon)
+        } else {
+            (get_latin_not_packons(), ModifierType::NotPackon)
+        };
+
+        for packon in packons {
+            if let Some(rest) = split_word.strip_suffix(packon.orth.as_str()) {
+                if rest.is_empty() {
+                    continue;
+                }
+
+                let mut new_modifiers = modifiers.clone();
+                new_modifiers.push(attachment_modifier(packon, modifier_type));
+                with_packons.push((rest.to_string(), new_modifiers));
+            }
+        }
+    }
+    splits.extend(with_packons);
+
+    splits.retain(|(_, modifiers)| !modifiers.is_empty());
+    splits.sort_by_key(|(_, modifiers)| modifiers.len());
+    splits
+}
+
+/**
+ * Whether an addon can be attached to the word, ex: cum is only attached to personal pronouns (mecum, nobiscum).
+ */
+pub fn attachment_fits(modifier: &Modifier, pos: PartOfSpeech) -> bool {
+    match modifier.pos {
+        PartOfSpeech::Unknown | PartOfSpeech::Packon | PartOfSpeech::Tackon => true,
+        attachment_pos => attachment_pos == pos,
+    }
 }
@@ -1,9 +1,14 @@
 pub mod english_to_latin;
+pub mod filter;
 pub mod latin_to_english;
 
 use crate::dictionary_structures::dictionary_values::Form;
 use crate::translators::english_to_latin::EnglishTranslationInfo;
+use crate::translators::latin_to_english::phrases::PhraseMatch;
+use crate::translators::latin_to_english::suggestions::Suggestion;
 use crate::translators::latin_to_english::LatinTranslationInfo;
+use crate::utils::ocr::Correction;
+use crate::utils::tokenizer::SourceSpan;
 use serde::{Deserialize, Serialize, Serializer};
 use std::mem::take;
 
@@ -36,8 +41,18 @@ pub enum Structure {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct Translation {
     pub word: String,
+    // the word as it was looked up, in lower case and without punctuation
+    pub normalized: Option<String>,
+    // where the word is in the text that was translated
+    pub span: Option<SourceSpan>,
     #[serde(serialize_with = "serialize_translation")]
     pub definitions: TranslationType,
+    // close words that are in the dictionary, only given for Latin words that were not found
+    pub suggestions: Option<Vec<Suggestion>>,
+    // changes made to the word when cleaning scanned text
+    pub corrections: Option<Vec<Correction>>,
+    // the phrases of the phrase lexicon the word is part of, only given for Latin
+    pub phrases: Option<Vec<PhraseMatch>>,
 }
 
 fn serialize_translation<S>(def: &TranslationType, serializer: S) -> Result<S::Ok, S::Error>
@@ -52,7 +67,15 @@ where
 
 impl Translation {
     pub fn new(word: String, definitions: TranslationType) -> Translation {
-        Translation { word, definitions }
+        Translation {
+            word,
+            normalized: None,
+            span: None,
+            definitions,
+            suggestions: None,
+            corrections: None,
+            phrases: None,
+        }
     }
 
     pub fn as_json(&self) -> String {
@@ -76,34 +99,7 @@ impl Translation {
                     TranslationType::Latin(definitions) => {
                         let new_definitions: Vec<_> = definitions
                             .drain(..)
-                            .map(|mut definition| {
-                                definition.word.form.str_form_to_long_form(
-                                    definition.word.pos,
-                                    Structure::LatinWordInfo,
-                                );
-
-                                definition.word.generate_principle_parts();
-
-                                definition
-                                    .stem
-                                    .form
-                                    .str_form_to_long_form(definition.word.pos, Structure::Stem);
-
-                                if let Some(mut inflections) = take(&mut definition.inflections) {
-                                    definition.remove_inflections_with_wrong_pos();
-                                    let part_of_speech = definition.word.pos;
-
-                                    inflections.iter_mut().for_each(|inflection| {
-                                        inflection.form.str_form_to_long_form(
-                                            part_of_speech,
-                                            Structure::Inflection,
-                                        );
-                                    });
-
-                                    definition.inflections = Some(inflections);
-                                }
-                                definition
-                            })
+                            .map(process_latin_definition)
                             .collect();
                         TranslationType::Latin(new_definitions)
                     }
@@ -112,6 +108,16 @@ impl Translation {
                         std::process::exit(0);
                     }
                 };
+
+                if let Some(suggestions) = &mut processed_translation.suggestions {
+                    for suggestion in suggestions.iter_mut() {
+                        suggestion.definitions = suggestion
+                            .definitions
+                            .drain(..)
+                            .map(process_latin_definition)
+                            .collect();
+                    }
+                }
             }
             Language::English => {
                 processed_translation.definitions = match &mut processed_translation.definitions {
@@ -162,9 +168,13 @@ impl Translation {
         match display_type {
             DisplayType::Pretty(detailed) => match &self.definitions {
                 TranslationType::Latin(definitions) => {
+                    if let Some(corrections) = &self.corrections {
+                        for correction in corrections {
+                            println!("{correction}");
+                        }
+                    }
                     if definitions.is_empty() {
                         println!("No definitions found");
-                        return;
                     }
                     for definition in definitions {
                         if definition.tricks.is_some() {
@@ -218,6 +228,16 @@ impl Translation {
                             }
                         }
 
+                        if let Some(derived) = &definition.derived {
+                            println!("derived {}: {}", derived.pos.as_str(), derived.form);
+                        }
+
+                        if let Some(parts) = &definition.compound {
+                            let orths: Vec<&str> =
+                                parts.iter().map(|part| part.word.orth.as_str()).collect();
+                            println!("compound: {}", orths.join(" + "));
+                        }
+
                         if detailed {
                             println!("{}", definition.word.info.as_str());
                         }
@@ -238,6 +258,26 @@ impl Translation {
 
                         println!("\n");
                     }
+
+                    if let Some(phrases) = &self.phrases {
+                        for phrase in phrases {
+                            println!("phrase '{}': {}", phrase.phrase, phrase.senses.join(" "));
+                        }
+                        println!();
+                    }
+
+                    if let Some(suggestions) = &self.suggestions {
+                        if !suggestions.is_empty() {
+                            println!("Did you mean:");
+                        }
+                        for suggestion in suggestions {
+                            let senses = match suggestion.definitions.first() {
+                                Some(definition) => definition.word.senses.join(" "),
+                                None => String::new(),
+                            };
+                            println!("{} | {senses}", suggestion.word);
+                        }
+                    }
                 }
                 TranslationType::English(definitions) => {
                     if definitions.is_empty() {
@@ -277,6 +317,42 @@ impl Translation {
     }
 }
 
+/**
+ * Expands the forms of a Latin definition, and of the parts it was made of if it is a compound.
+ */
+pub fn process_latin_definition(mut definition: LatinTranslationInfo) -> LatinTranslationInfo {
+    definition
+        .word
+        .form
+        .str_form_to_long_form(definition.word.pos, Structure::LatinWordInfo);
+
+    definition.word.generate_principle_parts();
+
+    definition
+        .stem
+        .form
+        .str_form_to_long_form(definition.word.pos, Structure::Stem);
+
+    if let Some(mut inflections) = take(&mut definition.inflections) {
+        definition.remove_inflections_with_wrong_pos();
+        let part_of_speech = definition.get_pos();
+
+        inflections.iter_mut().for_each(|inflection| {
+            inflection
+                .form
+                .str_form_to_long_form(part_of_speech, Structure::Inflection);
+        });
+
+        definition.inflections = Some(inflections);
+    }
+
+    if let Some(parts) = take(&mut definition.compound) {
+        definition.compound = Some(parts.into_iter().map(process_latin_definition).collect());
+    }
+
+    definition
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(tag = "type")]
 pub enum TranslationType {
@@ -1,8 +1,15 @@
 use crate::dictionary_structures::dictionary_values::{
-    Attachment, EnglishWordInfo, Inflection, LatinWordInfo, Modifier, Stem, UniqueLatinWordInfo,
+    Assimilation, Attachment, EnglishWordInfo, Inflection, LatinName, LatinPhrase, LatinWordInfo,
+    Modifier, Stem, UniqueLatinWordInfo,
 };
+use crate::translators::latin_to_english::tricks::trick_lists::TrickTables;
+use crate::Error;
+use std::collections::HashMap;
 use std::{include_bytes, sync::OnceLock};
 
+static LATIN_TRICKS: OnceLock<TrickTables> = OnceLock::new();
+static LATIN_NAMES: OnceLock<Vec<LatinName>> = OnceLock::new();
+
 pub fn get_english_dictionary() -> &'static [EnglishWordInfo] {
     static DICTIONARY: OnceLock<Vec<EnglishWordInfo>> = OnceLock::new();
     DICTIONARY.get_or_init(|| {
@@ -19,6 +26,19 @@ pub fn get_latin_dictionary() -> &'static [LatinWordInfo] {
     })
 }
 
+/**
+ * The words of the latin dictionary by their id.
+ */
+pub fn get_latin_dictionary_by_id() -> &'static HashMap<i32, &'static LatinWordInfo> {
+    static DICTIONARY_BY_ID: OnceLock<HashMap<i32, &'static LatinWordInfo>> = OnceLock::new();
+    DICTIONARY_BY_ID.get_or_init(|| {
+        get_latin_dictionary()
+            .iter()
+            .map(|word| (word.id, word))
+            .collect()
+    })
+}
+
 pub fn get_unique_latin_words() -> &'static [LatinWordInfo] {
     static UNIQUE_LATIN_WORDS: OnceLock<Vec<LatinWordInfo>> = OnceLock::new();
     UNIQUE_LATIN_WORDS.get_or_init(|| {
@@ -104,3 +124,79 @@ pub fn get_latin_tickons() -> &'static [Attachment] {
         serde_json::from_slice(latin_tickons_json).unwrap()
     })
 }
+
+pub fn get_latin_assimilations() -> &'static [Assimilation] {
+    static ASSIMILATIONS: OnceLock<Vec<Assimilation>> = OnceLock::new();
+    ASSIMILATIONS.get_or_init(|| {
+        let latin_assimilations_json = include_bytes!("../dictionary/latin_assimilations.json");
+        serde_json::from_slice(latin_assimilations_json).unwrap()
+    })
+}
+
+pub fn get_latin_phrases() -> &'static [LatinPhrase] {
+    static PHRASES: OnceLock<Vec<LatinPhrase>> = OnceLock::new();
+    PHRASES.get_or_init(|| {
+        let latin_phrases_json = include_bytes!("../dictionary/latin_phrases.json");
+        serde_json::from_slice(latin_phrases_json).unwrap()
+    })
+}
+
+pub fn get_latin_tricks() -> &'static TrickTables {
+    LATIN_TRICKS.get_or_init(get_default_latin_tricks)
+}
+
+fn get_default_latin_tricks() -> TrickTables {
+    let latin_tricks_json = include_bytes!("../dictionary/latin_tricks.json");
+    serde_json::from_slice(latin_tricks_json).unwrap()
+}
+
+/**
+ * Adds the tricks in each file to the ones in latin_tricks.json.
+ * Has to be called before any word is translated, as the tricks can only be loaded once.
+ */
+pub fn load_user_tricks(trick_files: &[String]) -> anyhow::Result<()> {
+    let mut tricks = get_default_latin_tricks();
+
+    for trick_file in trick_files {
+        let user_tricks_json = std::fs::read(trick_file)
+            .map_err(|err| Error::InvalidTrickFile(format!("{trick_file}: {err}")))?;
+        let user_tricks: TrickTables = serde_json::from_slice(&user_tricks_json)
+            .map_err(|err| Error::InvalidTrickFile(format!("{trick_file}: {err}")))?;
+        tricks.extend(user_tricks);
+    }
+
+    LATIN_TRICKS
+        .set(tricks)
+        .map_err(|_| Error::TricksAlreadyLoaded)?;
+
+    Ok(())
+}
+
+/**
+ * The names from the names lists the user gave, there are none unless `load_user_names` was called.
+ */
+pub fn get_latin_names() -> &'static [LatinName] {
+    LATIN_NAMES.get().map_or(&[], |names| names.as_slice())
+}
+
+/**
+ * Loads the names in each file, which are checked before the dictionary.
+ * Has to be called before any word is translated, as the names can only be loaded once.
+ */
+pub fn load_user_names(name_files: &[String]) -> anyhow::Result<()> {
+    let mut names: Vec<LatinName> = Vec::new();
+
+    for name_file in name_files {
+        let user_names_json = std::fs::read(name_file)
+            .map_err(|err| Error::InvalidNamesFile(format!("{name_file}: {err}")))?;
+        let user_names: Vec<LatinName> = serde_json::from_slice(&user_names_json)
+            .map_err(|err| Error::InvalidNamesFile(format!("{name_file}: {err}")))?;
+        names.extend(user_names);
+    }
+
+    LATIN_NAMES
+        .set(names)
+        .map_err(|_| Error::NamesAlreadyLoaded)?;
+
+    Ok(())
+}
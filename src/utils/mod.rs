@@ -1,7 +1,9 @@
 use crate::Error;
 
 pub mod data;
+pub mod ocr;
 pub mod principle_part_generator;
+pub mod tokenizer;
 pub mod type_translator;
 
 /**
@@ -0,0 +1,306 @@
+use crate::translators::latin_to_english::parser::parse;
+use crate::translators::latin_to_english::suggestions::CONFUSIONS;
+use crate::utils::is_roman_number;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+const LIGATURES: [(char, &str); 6] = [
+    ('æ', "ae"),
+    ('Æ', "Ae"),
+    ('œ', "oe"),
+    ('Œ', "Oe"),
+    ('ﬁ', "fi"),
+    ('ﬂ', "fl"),
+];
+// brackets editors put around letters they added or think should be removed, the letters are kept
+const EDITORIAL_BRACKETS: [char; 10] = ['[', ']', '⟨', '⟩', '<', '>', '{', '}', '⟦', '⟧'];
+// marks for corrupt passages, notes and the apparatus
+const SIGLA: [char; 16] = [
+    '†', '‡', '*', '‖', '¶', '§', '⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹',
+];
+// ¬ is often used for words broken across lines in older prints
+const HYPHENS: [char; 3] = ['-', '‐', '¬'];
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CorrectionKind {
+    Hyphenation,
+    Ligature,
+    LongS,
+    Confusion,
+    Bracket,
+    Siglum,
+}
+
+/**
+ * One change made to scanned text before it is translated.
+ * `position` is the byte offset of `original` in the text that was given.
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Correction {
+    pub kind: CorrectionKind,
+    pub original: String,
+    pub replacement: String,
+    pub position: usize,
+    pub explanation: String,
+}
+
+impl Correction {
+    pub fn new(
+        kind: CorrectionKind,
+        original: &str,
+        replacement: &str,
+        position: usize,
+        explanation: &str,
+    ) -> Correction {
+        Correction {
+            kind,
+            original: original.to_string(),
+            replacement: replacement.to_string(),
+            position,
+            explanation: explanation.to_string(),
+        }
+    }
+}
+
+impl Display for Correction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.explanation)
+    }
+}
+
+/**
+ * Text with the OCR mistakes fixed, and every correction that was made, in the order of the text.
+ * `source_offsets` has the byte of the given text that each byte of `text` came from.
+ */
+#[derive(Debug, Clone, Default)]
+pub struct CleanedText {
+    pub text: String,
+    pub corrections: Vec<Correction>,
+    pub source_offsets: Vec<usize>,
+    pub source: String,
+}
+
+impl CleanedText {
+    /**
+     * The byte of the given text a byte of the cleaned text came from, the end of the given text for the end of the cleaned text.
+     */
+    pub fn source_offset(&self, offset: usize) -> usize {
+        self.source_offsets
+            .get(offset)
+            .copied()
+            .unwrap_or(self.source.len())
+    }
+
+    /**
+     * The bytes of the given text that a range of bytes of the cleaned text came from.
+     */
+    pub fn source_range(&self, start: usize, end: usize) -> (usize, usize) {
+        let source_start = self.source_offset(start);
+        if end <= start {
+            return (source_start, source_start);
+        }
+
+        // the last byte comes from the start of a character of the given text, which has to be included whole
+        let last = self.source_offset(end - 1);
+        let last_length = self.source[last..].chars().next().map_or(0, char::len_utf8);
+        (source_start, last + last_length)
+    }
+
+    fn push(&mut self, text: &str, source_offset: usize) {
+        self.text.push_str(text);
+        self.source_offsets
+            .extend(std::iter::repeat_n(source_offset, text.len()));
+    }
+}
+
+/**
+ * Cleans text from scanned editions before it is translated:
+ * rejoins words broken across lines, replaces ligatures and the long s, removes editorial brackets and sigla,
+ * then fixes OCR mistakes (rn for m, n for u, ...) in words that are only found once fixed.
+ */
+pub fn clean_ocr_text(text: &str) -> CleanedText {
+    let mut cleaned = clean_characters(text);
+    fix_confusions(&mut cleaned);
+    cleaned
+        .corrections
+        .sort_by_key(|correction| correction.position);
+    cleaned
+}
+
+fn clean_characters(text: &str) -> CleanedText {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut cleaned = CleanedText {
+        source: text.to_string(),
+        ..Default::default()
+    };
+
+    let mut i = 0;
+    while i < chars.len() {
+        let (position, c) = chars[i];
+        i += 1;
+
+        if HYPHENS.contains(&c) {
+            if let Some(next_line) = line_break_end(&chars, i - 1) {
+                let end = chars[next_line].0;
+                cleaned.corrections.push(Correction::new(
+                    CorrectionKind::Hyphenation,
+                    &text[position..end],
+                    "",
+                    position,
+                    "Rejoined a word broken across lines",
+                ));
+                i = next_line;
+                continue;
+            }
+        }
+
+        if let Some((ligature, replacement)) = LIGATURES.iter().find(|(ligature, _)| *ligature == c)
+        {
+            cleaned.push(replacement, position);
+            cleaned.corrections.push(Correction::new(
+                CorrectionKind::Ligature,
+                &ligature.to_string(),
+                replacement,
+                position,
+                &format!("Replaced the ligature '{ligature}' with '{replacement}'"),
+            ));
+            continue;
+        }
+
+        if c == 'ſ' {
+            cleaned.push("s", position);
+            cleaned.corrections.push(Correction::new(
+                CorrectionKind::LongS,
+                "ſ",
+                "s",
+                position,
+                "Replaced the long s 'ſ' with 's'",
+            ));
+            continue;
+        }
+
+        if EDITORIAL_BRACKETS.contains(&c) {
+            cleaned.corrections.push(Correction::new(
+                CorrectionKind::Bracket,
+                &c.to_string(),
+                "",
+                position,
+                &format!("Removed the editorial bracket '{c}'"),
+            ));
+            continue;
+        }
+
+        if SIGLA.contains(&c) {
+            cleaned.corrections.push(Correction::new(
+                CorrectionKind::Siglum,
+                &c.to_string(),
+                "",
+                position,
+                &format!("Removed the siglum '{c}'"),
+            ));
+            continue;
+        }
+
+        cleaned.push(&c.to_string(), position);
+    }
+
+    cleaned
+}
+
+/**
+ * Whether the hyphen breaks a word across lines (ama-\nvit), giving the index of the first letter on the next line.
+ */
+fn line_break_end(chars: &[(usize, char)], hyphen: usize) -> Option<usize> {
+    if hyphen == 0 || !chars[hyphen - 1].1.is_alphabetic() {
+        return None;
+    }
+
+    let mut i = hyphen + 1;
+    while i < chars.len() && matches!(chars[i].1, ' ' | '\t' | '\r') {
+        i += 1;
+    }
+
+    if i >= chars.len() || chars[i].1 != '\n' {
+        return None;
+    }
+
+    while i < chars.len() && chars[i].1.is_whitespace() {
+        i += 1;
+    }
+
+    if i < chars.len() && chars[i].1.is_alphabetic() {
+        Some(i)
+    } else {
+        None
+    }
+}
+
+/**
+ * Fixes one OCR mistake in each word that is not found, if the fixed word is found.
+ * Words are fixed from the last to the first so the offsets of the ones before do not change.
+ */
+fn fix_confusions(cleaned: &mut CleanedText) {
+    let mut word_spans: Vec<(usize, usize)> = Vec::new();
+    let mut word_start: Option<usize> = None;
+
+    for (offset, c) in cleaned.text.char_indices() {
+        match (c.is_alphabetic(), word_start) {
+            (true, None) => word_start = Some(offset),
+            (false, Some(start)) => {
+                word_spans.push((start, offset));
+                word_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = word_start {
+        word_spans.push((start, cleaned.text.len()));
+    }
+
+    for (start, end) in word_spans.into_iter().rev() {
+        let word = cleaned.text[start..end].to_string();
+        if word.chars().count() < 2
+            || is_roman_number(&word)
+            || parse(&word.to_lowercase(), false).is_some()
+        {
+            continue;
+        }
+
+        let (offset, from, to) = match find_confusion(&word) {
+            Some(confusion) => confusion,
+            None => continue,
+        };
+
+        let source_offset = cleaned.source_offset(start + offset);
+        let range = start + offset..start + offset + from.len();
+        cleaned.text.replace_range(range.clone(), to);
+        cleaned
+            .source_offsets
+            .splice(range, std::iter::repeat_n(source_offset, to.len()));
+        cleaned.corrections.push(Correction::new(
+            CorrectionKind::Confusion,
+            from,
+            to,
+            source_offset,
+            &format!("Read '{from}' as '{to}', a common OCR mistake"),
+        ));
+    }
+}
+
+// the first OCR mistake that gives a word that is found, as the offset in the word and the letters to swap
+fn find_confusion(word: &str) -> Option<(usize, &'static str, &'static str)> {
+    for &(from, to) in CONFUSIONS.iter() {
+        for (from, to) in [(from, to), (to, from)] {
+            for (offset, _) in word.match_indices(from) {
+                let candidate = format!("{}{to}{}", &word[..offset], &word[offset + from.len()..]);
+
+                if parse(&candidate.to_lowercase(), false).is_some() {
+                    return Some((offset, from, to));
+                }
+            }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,238 @@
+use crate::utils::{convert_number_to_roman_numeral, evaluate_roman_numeral, is_roman_number};
+use serde::{Deserialize, Serialize};
+
+// apostrophes between letters are part of the word, ex: elisions like factum'st
+const APOSTROPHES: [char; 2] = ['\'', '’'];
+// the colon and semicolon are counted too, as they end a clause a phrase should not go over
+const SENTENCE_ENDS: [&str; 6] = [".", "?", "!", ";", ":", "·"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenKind {
+    Word,
+    Number,
+    RomanNumeral,
+    Punctuation,
+    Whitespace,
+}
+
+/**
+ * A piece of text and where it is in the text it was taken from.
+ * The text can be put back together from its tokens, punctuation and whitespace included.
+ */
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub text: String,
+    pub span: SourceSpan,
+}
+
+/**
+ * Where a piece of text is in a longer text.
+ * `start` and `end` are byte offsets, `start_char` and `end_char` are character offsets,
+ * `line` and `column` are where it starts, both counted from 1 with the column in characters.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+pub struct SourceSpan {
+    pub start: usize,
+    pub end: usize,
+    pub start_char: usize,
+    pub end_char: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl SourceSpan {
+    /**
+     * The spans of byte ranges of the text, the ranges have to be in order and not overlap.
+     * The text is only gone through once, so that long texts with many words stay fast.
+     */
+    pub fn from_byte_ranges(text: &str, ranges: &[(usize, usize)]) -> Vec<SourceSpan> {
+        let mut spans: Vec<SourceSpan> = Vec::new();
+        let mut chars = text.char_indices().peekable();
+        let (mut char_index, mut line, mut column) = (0, 1, 1);
+
+        // moves past every character before the byte offset
+        let mut advance_to = |offset: usize| {
+            while let Some(&(char_offset, c)) = chars.peek() {
+                if char_offset >= offset {
+                    break;
+                }
+
+                chars.next();
+                char_index += 1;
+                if c == '\n' {
+                    line += 1;
+                    column = 1;
+                } else {
+                    column += 1;
+                }
+            }
+
+            (char_index, line, column)
+        };
+
+        for &(start, end) in ranges {
+            let (start_char, line, column) = advance_to(start);
+            let (end_char, _, _) = advance_to(end);
+
+            spans.push(SourceSpan {
+                start,
+                end,
+                start_char,
+                end_char,
+                line,
+                column,
+            });
+        }
+
+        spans
+    }
+
+    /**
+     * The span from the start of this one to the end of another one after it.
+     */
+    pub fn to(&self, end: &SourceSpan) -> SourceSpan {
+        SourceSpan {
+            end: end.end,
+            end_char: end.end_char,
+            ..*self
+        }
+    }
+}
+
+impl Token {
+    /**
+     * Whether the token should be looked up, words and roman numerals are, everything else is only kept for the text around them.
+     */
+    pub fn is_word(&self) -> bool {
+        matches!(self.kind, TokenKind::Word | TokenKind::RomanNumeral)
+    }
+
+    /**
+     * Whether the token is punctuation that ends a sentence or a clause.
+     */
+    pub fn ends_sentence(&self) -> bool {
+        self.kind == TokenKind::Punctuation && SENTENCE_ENDS.contains(&self.text.as_str())
+    }
+}
+
+/**
+ * Splits text into words, numbers, roman numerals, punctuation and whitespace.
+ * Each punctuation mark is its own token, runs of whitespace are one token.
+ */
+pub fn tokenize(text: &str) -> Vec<Token> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut pieces: Vec<(TokenKind, usize, usize)> = Vec::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        let (start, c) = chars[i];
+        let kind = if c.is_whitespace() {
+            TokenKind::Whitespace
+        } else if c.is_alphabetic() {
+            TokenKind::Word
+        } else if c.is_numeric() {
+            TokenKind::Number
+        } else {
+            TokenKind::Punctuation
+        };
+
+        i += 1;
+        if kind != TokenKind::Punctuation {
+            while i < chars.len() && continues_token(kind, &chars, i) {
+                i += 1;
+            }
+        }
+
+        let end = chars.get(i).map_or(text.len(), |(offset, _)| *offset);
+        let token_text = &text[start..end];
+
+        let kind = if kind == TokenKind::Word && is_roman_numeral(token_text) {
+            TokenKind::RomanNumeral
+        } else {
+            kind
+        };
+
+        pieces.push((kind, start, end));
+    }
+
+    let ranges: Vec<(usize, usize)> = pieces.iter().map(|&(_, start, end)| (start, end)).collect();
+    let spans = SourceSpan::from_byte_ranges(text, &ranges);
+
+    pieces
+        .into_iter()
+        .zip(spans)
+        .map(|((kind, start, end), span)| Token {
+            kind,
+            text: text[start..end].to_string(),
+            span,
+        })
+        .collect()
+}
+
+/**
+ * The tokens of English text that should be looked up, in the order of the text.
+ * Numbers are kept, as they are translated to roman numerals.
+ */
+pub fn english_word_tokens(text: &str) -> Vec<Token> {
+    tokenize(text)
+        .into_iter()
+        .filter(|token| token.is_word() || token.kind == TokenKind::Number)
+        .collect()
+}
+
+/**
+ * The tokens that should be looked up, each with the number of the sentence it is in, counted from 0.
+ */
+pub fn sentence_word_tokens(text: &str) -> Vec<(usize, Token)> {
+    let mut sentence = 0;
+    let mut words: Vec<(usize, Token)> = Vec::new();
+
+    for token in tokenize(text) {
+        if token.ends_sentence() {
+            sentence += 1;
+        } else if token.is_word() {
+            words.push((sentence, token));
+        }
+    }
+
+    words
+}
+
+fn continues_token(kind: TokenKind, chars: &[(usize, char)], i: usize) -> bool {
+    let c = chars[i].1;
+
+    match kind {
+        TokenKind::Whitespace => c.is_whitespace(),
+        TokenKind::Number => c.is_numeric(),
+        TokenKind::Word => {
+            c.is_alphabetic()
+                || (APOSTROPHES.contains(&c)
+                    && chars
+                        .get(i + 1)
+                        .is_some_and(|(_, next)| next.is_alphabetic()))
+        }
+        TokenKind::Punctuation | TokenKind::RomanNumeral => false,
+    }
+}
+
+/**
+ * Roman numerals are written in capitals in the texts, and only ones written the usual way are taken as numerals,
+ * so that DIC, or vi (from vis), are still read as words.
+ * A single letter is not a numeral, as it is more often a word or an abbreviation (I, C. for Gaius).
+ */
+fn is_roman_numeral(word: &str) -> bool {
+    if word.chars().count() < 2
+        || !word.chars().all(|c| c.is_ascii_uppercase())
+        || !is_roman_number(word)
+    {
+        return false;
+    }
+
+    match evaluate_roman_numeral(word) {
+        Ok(number) if number > 0 => convert_number_to_roman_numeral(&number.to_string())
+            .is_ok_and(|numeral| numeral == word),
+        _ => false,
+    }
+}